@@ -0,0 +1,44 @@
+//! Bakes the content directory into the binary when `embed-content` is enabled
+
+#[path = "src/shell/content_walk.rs"]
+mod content_walk;
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use content_walk::WalkedKind;
+
+fn main() -> io::Result<()> {
+    println!("cargo:rerun-if-env-changed=PORTFOLIO_CONTENT");
+    if env::var_os("CARGO_FEATURE_EMBED_CONTENT").is_none() {
+        return Ok(());
+    }
+
+    // The same variable the binary reads at run time to load content from disk
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    let root = env::var_os("PORTFOLIO_CONTENT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| manifest_dir.join("content"));
+    println!("cargo:rerun-if-changed={}", root.display());
+
+    let mut out = String::from("&[\n");
+    for entry in content_walk::walk(&root)? {
+        let data = match entry.kind {
+            WalkedKind::Dir => "EmbeddedData::Dir".to_string(),
+            WalkedKind::File(path) => {
+                format!("EmbeddedData::File(include_bytes!({:?}))", path.display().to_string())
+            }
+            WalkedKind::Symlink(target) => format!("EmbeddedData::Symlink({:?})", target),
+        };
+        out.push_str(&format!(
+            "    EmbeddedNode {{ path: {:?}, data: {}, mode: {:#o}, mtime: {} }},\n",
+            entry.path, data, entry.mode, entry.mtime
+        ));
+    }
+    out.push(']');
+
+    let dest = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("content.rs");
+    fs::write(dest, out)
+}
@@ -0,0 +1,11 @@
+# Uchindami's Shell Profile
+export EDITOR=nvim
+export LANG=en_US.UTF-8
+
+# Prompt escapes: \u user, \h host, \w directory, \$ dollar sign
+PS1='\u@\h:\w\$ '
+
+# Aliases
+alias ll='ls -la'
+alias la='ls -a'
+alias ..='cd ..'
@@ -0,0 +1,71 @@
+#!/bin/rsh
+# A guided tour of the portfolio. Run it with ~/bin/tour.sh or sh ~/bin/tour.sh;
+# give it a section name (files, apps, blog, shell) to see just that part.
+
+section() {
+    echo
+    echo "== $1 =="
+    echo
+}
+
+files() {
+    section "Your home directory"
+    echo "Everything here is a file you can open. Start with the README:"
+    echo "  cat ~/README.md      or      glow ~/README.md"
+    echo
+    ls ~
+}
+
+apps() {
+    section "Apps"
+    echo "These open full-screen; press q to come back to the shell."
+    for app in about resume dashboard contact; do
+        if [ -x ~/$app ]; then
+            echo "  ./$app"
+        fi
+    done
+}
+
+blog() {
+    section "Blog"
+    for post in ~/blog/*/*.md; do
+        echo "  $post"
+    done
+    echo
+    echo "Read one with: less ~/latest-post.md"
+}
+
+shell() {
+    section "The shell"
+    echo "It is a small Unix shell: pipes, redirects, variables, aliases,"
+    echo "functions and scripts like this one all work. Some things to try:"
+    echo "  grep -ri rust ~/blog | head"
+    echo "  find ~ -name '*.md'"
+    echo "  man -k file"
+    echo "  alias"
+    echo "  cat ~/bin/tour.sh"
+}
+
+if [ $# -eq 0 ]; then
+    echo "Welcome to the tour, $USER!"
+    for part in files apps blog shell; do
+        $part
+    done
+    section "That's it"
+    echo "Type help for every command, or man <command> for the details."
+else
+    for part in "$@"; do
+        found=no
+        for known in files apps blog shell; do
+            if [ $part = $known ]; then
+                found=yes
+            fi
+        done
+        if [ $found = yes ]; then
+            $part
+        else
+            echo "tour: no section called $part (try files, apps, blog or shell)" >&2
+            exit 1
+        fi
+    done
+fi
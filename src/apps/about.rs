@@ -131,8 +131,4 @@ impl App for AboutApp {
         ]));
         frame.render_widget(footer, bio_layout[1]);
     }
-
-    fn name(&self) -> &'static str {
-        "About"
-    }
 }
@@ -139,8 +139,4 @@ impl App for ContactApp {
 
         frame.render_widget(qr, layout[1]);
     }
-
-    fn name(&self) -> &'static str {
-        "Contact"
-    }
 }
@@ -168,9 +168,5 @@ impl App for DashboardApp {
         ]));
         frame.render_widget(footer, layout[2]);
     }
-
-    fn name(&self) -> &'static str {
-        "Dashboard"
-    }
 }
 
@@ -0,0 +1,283 @@
+//! Editor App - a small nano-style editor for files in the session filesystem
+
+use std::cell::Cell;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use super::App;
+use crate::theme::Theme;
+use crate::ui;
+
+pub struct EditorApp {
+    /// File name as the user typed it
+    name: String,
+    /// Resolved path in the virtual filesystem
+    path: String,
+    lines: Vec<String>,
+    /// Cursor row and column (in chars)
+    row: usize,
+    col: usize,
+    /// First visible row, kept in view of the cursor while rendering
+    scroll: Cell<usize>,
+    modified: bool,
+    /// Set after a first Ctrl-X with unsaved changes
+    confirm_quit: bool,
+    status: Option<String>,
+    /// Content waiting to be written by the shell
+    pending_save: Option<String>,
+}
+
+impl EditorApp {
+    pub fn new(name: String, path: String, content: &str) -> Self {
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        Self {
+            name,
+            path,
+            lines,
+            row: 0,
+            col: 0,
+            scroll: Cell::new(0),
+            modified: false,
+            confirm_quit: false,
+            status: None,
+            pending_save: None,
+        }
+    }
+
+    /// Take the content to save, if the user asked to save
+    pub fn take_save(&mut self) -> Option<(String, String)> {
+        self.pending_save.take().map(|content| (self.path.clone(), content))
+    }
+
+    /// Report the outcome of a save
+    pub fn save_finished(&mut self, result: Result<(), String>) {
+        match result {
+            Ok(()) => {
+                self.modified = false;
+                self.status = Some(format!("[ Wrote {} lines ]", self.lines.len()));
+            }
+            Err(e) => self.status = Some(format!("[ Error writing {}: {} ]", self.name, e)),
+        }
+    }
+
+    fn line_len(&self) -> usize {
+        self.lines[self.row].chars().count()
+    }
+
+    /// Byte offset of the cursor within the current line
+    fn byte_col(&self) -> usize {
+        let line = &self.lines[self.row];
+        line.char_indices().nth(self.col).map(|(i, _)| i).unwrap_or(line.len())
+    }
+
+    fn insert_char(&mut self, c: char) {
+        let at = self.byte_col();
+        self.lines[self.row].insert(at, c);
+        self.col += 1;
+        self.modified = true;
+    }
+
+    fn newline(&mut self) {
+        let at = self.byte_col();
+        let rest = self.lines[self.row].split_off(at);
+        self.lines.insert(self.row + 1, rest);
+        self.row += 1;
+        self.col = 0;
+        self.modified = true;
+    }
+
+    fn backspace(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            let at = self.byte_col();
+            self.lines[self.row].remove(at);
+            self.modified = true;
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len();
+            self.lines[self.row].push_str(&line);
+            self.modified = true;
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.col < self.line_len() {
+            let at = self.byte_col();
+            self.lines[self.row].remove(at);
+            self.modified = true;
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+            self.modified = true;
+        }
+    }
+
+    fn move_vertical(&mut self, delta: isize) {
+        let max = self.lines.len() - 1;
+        self.row = self.row.saturating_add_signed(delta).min(max);
+        self.col = self.col.min(self.line_len());
+    }
+
+    fn content(&self) -> String {
+        let mut content = self.lines.join("\n");
+        content.push('\n');
+        content
+    }
+}
+
+impl App for EditorApp {
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        self.status = None;
+
+        match key.code {
+            KeyCode::Char('x') if ctrl => {
+                if self.modified && !self.confirm_quit {
+                    self.confirm_quit = true;
+                    self.status = Some(
+                        "Unsaved changes! ^X again to discard them, ^S to save".to_string(),
+                    );
+                    return false;
+                }
+                return true;
+            }
+            KeyCode::Esc => {
+                return self.handle_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL));
+            }
+            KeyCode::Char('s') | KeyCode::Char('o') if ctrl => {
+                self.pending_save = Some(self.content());
+            }
+            KeyCode::Char('a') if ctrl => self.col = 0,
+            KeyCode::Char('e') if ctrl => self.col = self.line_len(),
+            KeyCode::Char('k') if ctrl => {
+                if self.lines.len() > 1 {
+                    self.lines.remove(self.row);
+                    self.row = self.row.min(self.lines.len() - 1);
+                } else {
+                    self.lines[0].clear();
+                }
+                self.col = self.col.min(self.line_len());
+                self.modified = true;
+            }
+            KeyCode::Char(c) if !ctrl => self.insert_char(c),
+            KeyCode::Tab => {
+                for _ in 0..4 {
+                    self.insert_char(' ');
+                }
+            }
+            KeyCode::Enter => self.newline(),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left => {
+                if self.col > 0 {
+                    self.col -= 1;
+                } else if self.row > 0 {
+                    self.row -= 1;
+                    self.col = self.line_len();
+                }
+            }
+            KeyCode::Right => {
+                if self.col < self.line_len() {
+                    self.col += 1;
+                } else if self.row + 1 < self.lines.len() {
+                    self.row += 1;
+                    self.col = 0;
+                }
+            }
+            KeyCode::Up => self.move_vertical(-1),
+            KeyCode::Down => self.move_vertical(1),
+            KeyCode::PageUp => self.move_vertical(-20),
+            KeyCode::PageDown => self.move_vertical(20),
+            KeyCode::Home => self.col = 0,
+            KeyCode::End => self.col = self.line_len(),
+            _ => {}
+        }
+
+        self.confirm_quit = false;
+        false
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        let title = format!(
+            " nano - {}{} ",
+            self.name,
+            if self.modified { " (modified)" } else { "" }
+        );
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::BORDER))
+            .title(title)
+            .title_style(Style::default().fg(Theme::SECONDARY).add_modifier(Modifier::BOLD));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)])
+            .split(inner);
+
+        // Keep the cursor row on screen
+        let height = layout[0].height as usize;
+        let mut scroll = self.scroll.get();
+        if self.row < scroll {
+            scroll = self.row;
+        } else if height > 0 && self.row >= scroll + height {
+            scroll = self.row + 1 - height;
+        }
+        self.scroll.set(scroll);
+
+        let cursor_style = Style::default().bg(Theme::SUCCESS).fg(Theme::BACKGROUND);
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(height)
+            .map(|(i, line)| {
+                if i != self.row {
+                    return Line::from(line.clone());
+                }
+                let before: String = line.chars().take(self.col).collect();
+                let cursor = line.chars().nth(self.col).unwrap_or(' ');
+                let after: String = line.chars().skip(self.col + 1).collect();
+                Line::from(vec![
+                    Span::raw(before),
+                    Span::styled(cursor.to_string(), cursor_style),
+                    Span::raw(after),
+                ])
+            })
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).style(Style::default().fg(Theme::FOREGROUND)),
+            layout[0],
+        );
+
+        let status = self.status.clone().unwrap_or_else(|| {
+            format!("{}  line {}/{}, col {}", self.path, self.row + 1, self.lines.len(), self.col + 1)
+        });
+        frame.render_widget(Paragraph::new(ui::muted_text(&status)), layout[1]);
+        frame.render_widget(
+            Paragraph::new(ui::footer_keybinds(&[
+                ("^S", "Save"),
+                ("^X", "Exit"),
+                ("^K", "Cut line"),
+                ("^A/^E", "Line start/end"),
+            ])),
+            layout[2],
+        );
+    }
+}
@@ -4,11 +4,15 @@
 mod about;
 mod contact;
 mod dashboard;
+mod editor;
+mod pager;
 mod resume;
 
 pub use about::AboutApp;
 pub use contact::ContactApp;
 pub use dashboard::DashboardApp;
+pub use editor::EditorApp;
+pub use pager::PagerApp;
 pub use resume::ResumeApp;
 
 use crossterm::event::KeyEvent;
@@ -21,7 +25,4 @@ pub trait App {
 
     /// Render the app
     fn render(&self, frame: &mut Frame);
-
-    /// Get the app name for display
-    fn name(&self) -> &'static str;
 }
@@ -0,0 +1,300 @@
+//! Pager App - `less`: scroll through long output with search
+
+use std::cell::Cell;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use super::App;
+use crate::theme::Theme;
+use crate::ui;
+
+/// A search the user has run, repeated by `n` and `N`
+struct Search {
+    pattern: String,
+    forward: bool,
+}
+
+pub struct PagerApp {
+    /// File name(s) shown in the title and status line
+    title: String,
+    lines: Vec<Line<'static>>,
+    /// First line on screen
+    top: usize,
+    /// Rows available for text, as of the last render
+    height: Cell<usize>,
+    line_numbers: bool,
+    search: Option<Search>,
+    /// Pattern being typed after `/` or `?`, with its direction
+    prompt: Option<(bool, String)>,
+    /// `-` was pressed and an option letter should follow
+    option_pending: bool,
+    status: Option<String>,
+}
+
+impl PagerApp {
+    pub fn new(title: String, lines: Vec<Line<'static>>) -> Self {
+        Self {
+            title,
+            lines,
+            top: 0,
+            height: Cell::new(1),
+            line_numbers: false,
+            search: None,
+            prompt: None,
+            option_pending: false,
+            status: None,
+        }
+    }
+
+    /// Scroll so `top` moves by `delta` lines, without running off either end
+    fn scroll(&mut self, delta: isize) {
+        let max = self.lines.len().saturating_sub(self.height.get());
+        self.top = self.top.saturating_add_signed(delta).min(max);
+    }
+
+    fn page(&self) -> isize {
+        self.height.get().max(1) as isize
+    }
+
+    /// Jump to the next line matching the current search. `reverse` flips
+    /// the search's own direction, for `N`.
+    fn find_next(&mut self, reverse: bool) {
+        let Some(search) = &self.search else {
+            self.status = Some("No previous search".to_string());
+            return;
+        };
+        let forward = search.forward != reverse;
+        let matches = |i: &usize| ui::line_to_string(&self.lines[*i]).contains(&search.pattern);
+        let found = if forward {
+            (self.top + 1..self.lines.len()).find(matches)
+        } else {
+            (0..self.top).rev().find(matches)
+        };
+        match found {
+            Some(i) => self.top = i,
+            None => self.status = Some("Pattern not found".to_string()),
+        }
+    }
+
+    /// Keys while typing a search pattern
+    fn prompt_key(&mut self, key: KeyEvent) {
+        let Some((forward, pattern)) = &mut self.prompt else {
+            return;
+        };
+        match key.code {
+            KeyCode::Enter => {
+                let (forward, pattern) = (*forward, std::mem::take(pattern));
+                self.prompt = None;
+                // Like less, an empty pattern repeats the last search and
+                // the search starts below the top line
+                if !pattern.is_empty() {
+                    self.search = Some(Search { pattern, forward });
+                } else if let Some(search) = &mut self.search {
+                    search.forward = forward;
+                }
+                self.find_next(false);
+            }
+            KeyCode::Esc => self.prompt = None,
+            // Backspacing past the start cancels, as in less
+            KeyCode::Backspace if pattern.is_empty() => self.prompt = None,
+            KeyCode::Backspace => {
+                pattern.pop();
+            }
+            KeyCode::Char(c) => pattern.push(c),
+            _ => {}
+        }
+    }
+
+    /// A line as displayed: optional line number, matches highlighted
+    fn display_line(&self, index: usize) -> Line<'static> {
+        let line = &self.lines[index];
+        let mut spans = Vec::new();
+        if self.line_numbers {
+            spans.push(Span::styled(
+                format!("{:>6} ", index + 1),
+                Style::default().fg(Theme::MUTED),
+            ));
+        }
+        match &self.search {
+            Some(search) if !search.pattern.is_empty() => {
+                spans.extend(highlight(line, &search.pattern));
+            }
+            _ => spans.extend(line.spans.iter().cloned()),
+        }
+        Line::from(spans).style(line.style)
+    }
+
+    fn status_line(&self) -> Line<'static> {
+        if let Some((forward, pattern)) = &self.prompt {
+            let prefix = if *forward { '/' } else { '?' };
+            return Line::from(vec![
+                Span::raw(format!("{}{}", prefix, pattern)),
+                Span::styled(" ", Style::default().bg(Theme::SUCCESS)),
+            ]);
+        }
+        if let Some(status) = &self.status {
+            return ui::warning_text(status);
+        }
+
+        let total = self.lines.len();
+        let bottom = (self.top + self.height.get()).min(total);
+        let position = if bottom >= total {
+            "(END)".to_string()
+        } else {
+            format!("{}%", bottom * 100 / total.max(1))
+        };
+        ui::muted_text(&format!(
+            "{}  lines {}-{}/{}  {}",
+            self.title,
+            (self.top + 1).min(total),
+            bottom,
+            total,
+            position
+        ))
+    }
+}
+
+/// Split `line`'s spans so every occurrence of `pattern` is highlighted
+fn highlight(line: &Line<'static>, pattern: &str) -> Vec<Span<'static>> {
+    let text = ui::line_to_string(line);
+    let ranges: Vec<(usize, usize)> = text
+        .match_indices(pattern)
+        .map(|(start, m)| (start, start + m.len()))
+        .collect();
+    if ranges.is_empty() {
+        return line.spans.clone();
+    }
+
+    let mark = Style::default().bg(Theme::WARNING).fg(Theme::BACKGROUND);
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for span in &line.spans {
+        let content = span.content.as_ref();
+        let (start, end) = (offset, offset + content.len());
+        let mut pos = start;
+        for &(from, to) in &ranges {
+            let (from, to) = (from.max(start), to.min(end));
+            if from >= to {
+                continue;
+            }
+            if from > pos {
+                spans.push(Span::styled(text[pos..from].to_string(), span.style));
+            }
+            spans.push(Span::styled(text[from..to].to_string(), span.style.patch(mark)));
+            pos = to;
+        }
+        if pos < end {
+            spans.push(Span::styled(text[pos..end].to_string(), span.style));
+        }
+        offset = end;
+    }
+    spans
+}
+
+impl App for PagerApp {
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.prompt.is_some() {
+            self.prompt_key(key);
+            return false;
+        }
+        self.status = None;
+
+        if self.option_pending {
+            self.option_pending = false;
+            match key.code {
+                KeyCode::Char('N') => self.line_numbers = !self.line_numbers,
+                _ => self.status = Some("Unknown option (try -N)".to_string()),
+            }
+            return false;
+        }
+
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let page = self.page();
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => return true,
+            KeyCode::Char('c') if ctrl => return true,
+            KeyCode::Char('d') if ctrl => self.scroll(page / 2),
+            KeyCode::Char('u') if ctrl => self.scroll(-page / 2),
+            KeyCode::Char('f') if ctrl => self.scroll(page),
+            KeyCode::Char('b') if ctrl => self.scroll(-page),
+            KeyCode::Char('j') | KeyCode::Char('e') | KeyCode::Down | KeyCode::Enter => {
+                self.scroll(1)
+            }
+            KeyCode::Char('k') | KeyCode::Char('y') | KeyCode::Up => self.scroll(-1),
+            KeyCode::Char(' ') | KeyCode::Char('f') | KeyCode::PageDown => self.scroll(page),
+            KeyCode::Char('b') | KeyCode::PageUp => self.scroll(-page),
+            KeyCode::Char('d') => self.scroll(page / 2),
+            KeyCode::Char('u') => self.scroll(-page / 2),
+            KeyCode::Char('g') | KeyCode::Char('<') | KeyCode::Home => self.top = 0,
+            KeyCode::Char('G') | KeyCode::Char('>') | KeyCode::End => {
+                self.top = self.lines.len().saturating_sub(self.height.get())
+            }
+            KeyCode::Char('/') => self.prompt = Some((true, String::new())),
+            KeyCode::Char('?') => self.prompt = Some((false, String::new())),
+            KeyCode::Char('n') => self.find_next(false),
+            KeyCode::Char('N') => self.find_next(true),
+            KeyCode::Char('-') => self.option_pending = true,
+            _ => {}
+        }
+        false
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::BORDER))
+            .title(format!(" less - {} ", self.title))
+            .title_style(Style::default().fg(Theme::SECONDARY).add_modifier(Modifier::BOLD));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1), Constraint::Length(1)])
+            .split(inner);
+
+        // Long lines wrap, so fill the screen row by row from the top line
+        let height = layout[0].height as usize;
+        let width = layout[0].width as usize;
+        self.height.set(height);
+        let mut rows = Vec::new();
+        for index in self.top..self.lines.len() {
+            if rows.len() >= height {
+                break;
+            }
+            rows.extend(ui::wrap_line(&self.display_line(index), width));
+        }
+        rows.truncate(height);
+        while rows.len() < height {
+            rows.push(Line::from(Span::styled("~", Style::default().fg(Theme::MUTED))));
+        }
+        frame.render_widget(
+            Paragraph::new(rows).style(Style::default().fg(Theme::FOREGROUND)),
+            layout[0],
+        );
+
+        frame.render_widget(Paragraph::new(self.status_line()), layout[1]);
+        frame.render_widget(
+            Paragraph::new(ui::footer_keybinds(&[
+                ("j/k", "Line"),
+                ("Space/b", "Page"),
+                ("g/G", "Top/End"),
+                ("/ ?", "Search"),
+                ("n/N", "Next/Prev"),
+                ("-N", "Line numbers"),
+                ("q", "Quit"),
+            ])),
+            layout[2],
+        );
+    }
+}
@@ -11,6 +11,7 @@ use ratatui::{
 
 use super::App;
 use crate::theme::Theme;
+use crate::ui;
 
 const SECTIONS: [(&str, &str); 7] = [
     ("Summary", "PROFESSIONAL SUMMARY"),
@@ -46,11 +47,6 @@ const PROJECTS: [(&str, &str, &str); 4] = [
     ),
 ];
 
-/// Create an OSC 8 hyperlink (clickable in modern terminals)
-fn osc8_link(url: &str, text: &str) -> String {
-    format!("\x1b]8;;{}\x07{}\x1b]8;;\x07", url, text)
-}
-
 pub struct ResumeApp {
     selected_section: usize,
     list_state: ListState,
@@ -168,7 +164,7 @@ impl ResumeApp {
                     lines.push(Line::from(format!("    {}", desc)));
                     
                     // OSC 8 clickable link (works in modern terminals)
-                    let link_text = osc8_link(url, "🔗 Open Link");
+                    let link_text = ui::osc8_link(url, "🔗 Open Link");
                     lines.push(Line::from(format!("    {}", link_text)));
                     lines.push(Line::from(""));
                 }
@@ -357,8 +353,4 @@ impl App for ResumeApp {
             .wrap(Wrap { trim: true });
         frame.render_widget(content, columns[1]);
     }
-
-    fn name(&self) -> &'static str {
-        "Resume"
-    }
 }
@@ -0,0 +1,414 @@
+//! Lightweight syntax highlighting for source files and fenced code
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::theme::Theme;
+
+const PLAIN: Style = Style::new();
+const KEYWORD: Style = Style::new().fg(Theme::ACCENT);
+const TYPE: Style = Style::new().fg(Theme::HIGHLIGHT);
+const FUNCTION: Style = Style::new().fg(Theme::WARNING);
+const STRING: Style = Style::new().fg(Theme::PRIMARY);
+const NUMBER: Style = Style::new().fg(Theme::SECONDARY);
+const COMMENT: Style = Style::new().fg(Theme::MUTED).add_modifier(Modifier::ITALIC);
+/// JSON and TOML keys
+const KEY: Style = Style::new().fg(Theme::HIGHLIGHT);
+/// TOML `[table]` headers
+const SECTION: Style = Style::new().fg(Theme::SECONDARY).add_modifier(Modifier::BOLD);
+/// Shell `$variables`
+const VARIABLE: Style = Style::new().fg(Theme::WARNING);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    Python,
+    /// JavaScript and TypeScript
+    JavaScript,
+    Shell,
+    Toml,
+    Json,
+}
+
+impl Language {
+    /// Language for a markdown fence tag or `bat -l` argument
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "rust" | "rs" => Some(Self::Rust),
+            "python" | "py" => Some(Self::Python),
+            "javascript" | "js" | "jsx" | "mjs" | "cjs" | "typescript" | "ts" | "tsx" => {
+                Some(Self::JavaScript)
+            }
+            "shell" | "sh" | "bash" | "zsh" => Some(Self::Shell),
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// Guess the language from a file name
+    pub fn from_path(path: &str) -> Option<Self> {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        if matches!(name, ".profile" | ".bashrc" | ".bash_profile" | ".zshrc") {
+            return Some(Self::Shell);
+        }
+        let (_, extension) = name.rsplit_once('.')?;
+        Self::from_name(extension)
+    }
+
+    fn syntax(self) -> &'static Syntax {
+        match self {
+            Self::Rust => &RUST,
+            Self::Python => &PYTHON,
+            Self::JavaScript => &JAVASCRIPT,
+            Self::Shell => &SHELL,
+            Self::Toml => &TOML,
+            Self::Json => &JSON,
+        }
+    }
+
+    /// Whether backslash escapes work inside strings opened by `delim`
+    fn escapes(self, delim: &str) -> bool {
+        !(delim.starts_with('\'') && matches!(self, Self::Shell | Self::Toml))
+    }
+}
+
+/// What a language's tokens look like
+struct Syntax {
+    keywords: &'static [&'static str],
+    constants: &'static [&'static str],
+    line_comment: Option<&'static str>,
+    block_comment: Option<(&'static str, &'static str)>,
+    /// String delimiters, longest first, and whether they may span lines
+    strings: &'static [(&'static str, bool)],
+    /// Capitalized names are types and names before `(` are calls
+    names: bool,
+}
+
+const RUST: Syntax = Syntax {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "type",
+        "unsafe", "use", "where", "while",
+    ],
+    constants: &["true", "false"],
+    line_comment: Some("//"),
+    block_comment: Some(("/*", "*/")),
+    strings: &[("\"", true), ("'", false)],
+    names: true,
+};
+
+const PYTHON: Syntax = Syntax {
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is",
+        "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with",
+        "yield",
+    ],
+    constants: &["True", "False", "None"],
+    line_comment: Some("#"),
+    block_comment: None,
+    strings: &[("\"\"\"", true), ("'''", true), ("\"", false), ("'", false)],
+    names: true,
+};
+
+const JAVASCRIPT: Syntax = Syntax {
+    keywords: &[
+        "as", "async", "await", "break", "case", "catch", "class", "const", "continue",
+        "debugger", "declare", "default", "delete", "do", "else", "enum", "export", "extends",
+        "finally", "for", "from", "function", "if", "implements", "import", "in", "instanceof",
+        "interface", "let", "new", "of", "private", "protected", "public", "readonly", "return",
+        "static", "super", "switch", "this", "throw", "try", "type", "typeof", "var", "void",
+        "while", "yield",
+    ],
+    constants: &["true", "false", "null", "undefined", "NaN"],
+    line_comment: Some("//"),
+    block_comment: Some(("/*", "*/")),
+    strings: &[("`", true), ("\"", false), ("'", false)],
+    names: true,
+};
+
+const SHELL: Syntax = Syntax {
+    keywords: &[
+        "if", "then", "else", "elif", "fi", "for", "while", "until", "do", "done", "case", "esac",
+        "in", "function", "return", "local", "export", "readonly", "unset", "select",
+    ],
+    constants: &[],
+    line_comment: Some("#"),
+    block_comment: None,
+    strings: &[("\"", false), ("'", false)],
+    names: false,
+};
+
+const TOML: Syntax = Syntax {
+    keywords: &[],
+    constants: &["true", "false"],
+    line_comment: Some("#"),
+    block_comment: None,
+    strings: &[("\"\"\"", true), ("'''", true), ("\"", false), ("'", false)],
+    names: false,
+};
+
+const JSON: Syntax = Syntax {
+    keywords: &[],
+    constants: &["true", "false", "null"],
+    line_comment: None,
+    block_comment: None,
+    strings: &[("\"", false)],
+    names: false,
+};
+
+/// A comment or string still open at the end of a line
+#[derive(Clone, Copy)]
+enum Open {
+    Comment(&'static str),
+    Str(&'static str),
+}
+
+/// Highlight `source` line by line
+pub fn highlight(source: &str, language: Language) -> Vec<Line<'static>> {
+    let mut open = None;
+    source
+        .lines()
+        .map(|line| highlight_line(line, language, &mut open))
+        .collect()
+}
+
+/// Spans of one line, merging neighbours that share a style
+#[derive(Default)]
+struct Spans(Vec<Span<'static>>);
+
+impl Spans {
+    fn push(&mut self, text: &str, style: Style) {
+        match self.0.last_mut() {
+            Some(last) if last.style == style => last.content.to_mut().push_str(text),
+            _ => self.0.push(Span::styled(text.to_string(), style)),
+        }
+    }
+}
+
+fn highlight_line(line: &str, language: Language, open: &mut Option<Open>) -> Line<'static> {
+    let syntax = language.syntax();
+    let mut out = Spans::default();
+    let mut i = 0;
+
+    // Finish a comment or string left open on an earlier line
+    if let Some(state) = open.take() {
+        let (end, style) = match state {
+            Open::Comment(end) => (line.find(end).map(|e| e + end.len()), COMMENT),
+            Open::Str(delim) => (find_close(line, 0, delim, language.escapes(delim)), STRING),
+        };
+        match end {
+            Some(end) => {
+                out.push(&line[..end], style);
+                i = end;
+            }
+            None => {
+                out.push(line, style);
+                *open = Some(state);
+                return Line::from(out.0);
+            }
+        }
+    }
+    if language == Language::Toml && i == 0 {
+        i = toml_key(line, &mut out);
+    }
+
+    while i < line.len() {
+        let rest = &line[i..];
+        let Some(c) = rest.chars().next() else {
+            break;
+        };
+
+        if let Some(marker) = syntax.line_comment
+            && rest.starts_with(marker)
+            && (language != Language::Shell || word_start(line, i))
+        {
+            out.push(rest, COMMENT);
+            break;
+        }
+
+        if let Some((start, end)) = syntax.block_comment
+            && rest.starts_with(start)
+        {
+            match rest[start.len()..].find(end) {
+                Some(e) => {
+                    let stop = i + start.len() + e + end.len();
+                    out.push(&line[i..stop], COMMENT);
+                    i = stop;
+                    continue;
+                }
+                None => {
+                    out.push(rest, COMMENT);
+                    *open = Some(Open::Comment(end));
+                    break;
+                }
+            }
+        }
+
+        if let Some(&(delim, multiline)) = syntax.strings.iter().find(|(d, _)| rest.starts_with(d))
+            && (language != Language::Rust || delim != "'" || is_char_literal(rest))
+        {
+            match find_close(line, i + delim.len(), delim, language.escapes(delim)) {
+                Some(stop) => {
+                    let is_key = language == Language::Json && line[stop..].trim_start().starts_with(':');
+                    out.push(&line[i..stop], if is_key { KEY } else { STRING });
+                    i = stop;
+                    continue;
+                }
+                None => {
+                    out.push(rest, STRING);
+                    if multiline {
+                        *open = Some(Open::Str(delim));
+                    }
+                    break;
+                }
+            }
+        }
+
+        if language == Language::Shell && c == '$' {
+            let len = variable_len(rest);
+            if len > 1 {
+                out.push(&rest[..len], VARIABLE);
+                i += len;
+                continue;
+            }
+        }
+
+        if c.is_ascii_digit() {
+            let len = number_len(rest);
+            out.push(&rest[..len], NUMBER);
+            i += len;
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..len];
+            let after = &rest[len..];
+            if syntax.names
+                && language == Language::Rust
+                && after.starts_with('!')
+                && !after.starts_with("!=")
+            {
+                // Macro call, `!` included
+                out.push(&rest[..len + 1], FUNCTION);
+                i += len + 1;
+                continue;
+            }
+            let style = if syntax.keywords.contains(&word) {
+                KEYWORD
+            } else if syntax.constants.contains(&word) {
+                NUMBER
+            } else if syntax.names && after.starts_with('(') {
+                FUNCTION
+            } else if syntax.names && word.starts_with(|c: char| c.is_uppercase()) {
+                TYPE
+            } else {
+                PLAIN
+            };
+            out.push(word, style);
+            i += len;
+            continue;
+        }
+
+        out.push(&rest[..c.len_utf8()], PLAIN);
+        i += c.len_utf8();
+    }
+
+    Line::from(out.0)
+}
+
+/// Byte offset just past the `delim` closing a string, searching from `from`
+fn find_close(line: &str, from: usize, delim: &str, escapes: bool) -> Option<usize> {
+    let mut chars = line[from..].char_indices();
+    while let Some((j, c)) = chars.next() {
+        if escapes && c == '\\' {
+            chars.next();
+            continue;
+        }
+        if line[from + j..].starts_with(delim) {
+            return Some(from + j + delim.len());
+        }
+    }
+    None
+}
+
+/// Whether the byte at `i` begins a shell word, where `#` starts a comment
+fn word_start(line: &str, i: usize) -> bool {
+    line[..i]
+        .chars()
+        .next_back()
+        .is_none_or(|prev| prev.is_whitespace() || matches!(prev, ';' | '|' | '&' | '('))
+}
+
+/// Rust `'a'` and `'\n'` are characters; `'a` on its own is a lifetime
+fn is_char_literal(rest: &str) -> bool {
+    let mut chars = rest[1..].chars();
+    match chars.next() {
+        Some('\\') => true,
+        Some(_) => chars.next() == Some('\''),
+        None => false,
+    }
+}
+
+/// Digits, hex and suffixes like `1_000u32` or `0x1F`, and one decimal point
+/// per run of digits so `0..10` stays a range
+fn number_len(rest: &str) -> usize {
+    let bytes = rest.as_bytes();
+    let mut len = 0;
+    while len < bytes.len() {
+        let b = bytes[len];
+        let decimal_point = b == b'.' && bytes.get(len + 1).is_some_and(u8::is_ascii_digit);
+        if !(b.is_ascii_alphanumeric() || b == b'_' || decimal_point) {
+            break;
+        }
+        len += 1;
+    }
+    len
+}
+
+/// Length of a `$name`, `${...}` or `$?`-style reference, or 1 for a lone `$`
+fn variable_len(rest: &str) -> usize {
+    let after = &rest[1..];
+    match after.chars().next() {
+        Some('{') => after.find('}').map_or(1, |end| end + 2),
+        Some(c) if c.is_alphabetic() || c == '_' => {
+            1 + after
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(after.len())
+        }
+        Some(c) if c.is_ascii_digit() || "?#@*!$-".contains(c) => 2,
+        _ => 1,
+    }
+}
+
+/// TOML keys and `[table]` headers at the start of a line. Returns where
+/// the rest of the line starts.
+fn toml_key(line: &str, out: &mut Spans) -> usize {
+    let indent = line.len() - line.trim_start().len();
+    let trimmed = &line[indent..];
+    if trimmed.starts_with('[') {
+        let end = match trimmed.find("]]") {
+            Some(end) if trimmed.starts_with("[[") => end + 2,
+            _ => match trimmed.find(']') {
+                Some(end) => end + 1,
+                None => return 0,
+            },
+        };
+        out.push(&line[..indent + end], SECTION);
+        return indent + end;
+    }
+    match trimmed.find('=') {
+        Some(eq) if !trimmed[..eq].trim().is_empty() && !trimmed[..eq].contains(['"', '\'', '#']) => {
+            out.push(&line[..indent + eq], KEY);
+            indent + eq
+        }
+        _ => 0,
+    }
+}
@@ -1,22 +1,33 @@
 //! Interactive Terminal Portfolio
 //! A shell-based TUI portfolio built with Ratatui
 
-use color_eyre::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
-use ratatui::{
-    style::Style,
-    widgets::{Block, Borders},
-    DefaultTerminal, Frame,
+use std::path::PathBuf;
+
+use color_eyre::{eyre::WrapErr, Result};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+    MouseEventKind,
 };
+use crossterm::execute;
+use ratatui::{DefaultTerminal, Frame};
 
 mod apps;
+mod highlight;
+mod markdown;
 mod shell;
 mod theme;
 mod ui;
 
-use apps::{AboutApp, App as SubApp, ContactApp, DashboardApp, ResumeApp};
-use shell::{Shell, ShellResult};
-use theme::Theme;
+use apps::{
+    AboutApp, App as SubApp, ContactApp, DashboardApp, EditorApp, PagerApp, ResumeApp,
+};
+use shell::{Shell, ShellResult, VirtualFS};
+
+/// Environment variable naming the content directory
+const CONTENT_ENV: &str = "PORTFOLIO_CONTENT";
+/// Content directory used when none is given and nothing is embedded
+#[cfg(not(feature = "embed-content"))]
+const DEFAULT_CONTENT_DIR: &str = "content";
 
 /// Current view state
 enum View {
@@ -25,16 +36,47 @@ enum View {
     Resume(ResumeApp),
     Contact(ContactApp),
     About(AboutApp),
+    Editor(EditorApp),
+    Pager(PagerApp),
 }
 
 fn main() -> Result<()> {
     color_eyre::install()?;
+    let fs = load_filesystem()?;
     let terminal = ratatui::init();
-    let result = App::new().run(terminal);
+    // Mouse capture is for wheel scrolling in the shell
+    execute!(std::io::stdout(), EnableMouseCapture)?;
+    let result = App::new(fs).run(terminal);
+    execute!(std::io::stdout(), DisableMouseCapture)?;
     ratatui::restore();
     result
 }
 
+/// Find the content root: `--content <dir>`, then `$PORTFOLIO_CONTENT`,
+/// then the embedded tree (or `./content` when built without it)
+fn load_filesystem() -> Result<VirtualFS> {
+    let mut args = std::env::args().skip(1);
+    let mut explicit = None;
+    while let Some(arg) = args.next() {
+        if arg == "--content" {
+            explicit = args.next().map(PathBuf::from);
+        } else if let Some(dir) = arg.strip_prefix("--content=") {
+            explicit = Some(PathBuf::from(dir));
+        }
+    }
+    let explicit = explicit.or_else(|| std::env::var_os(CONTENT_ENV).map(PathBuf::from));
+
+    let root = match explicit {
+        Some(root) => root,
+        #[cfg(feature = "embed-content")]
+        None => return VirtualFS::embedded().wrap_err("failed to load embedded content"),
+        #[cfg(not(feature = "embed-content"))]
+        None => PathBuf::from(DEFAULT_CONTENT_DIR),
+    };
+    VirtualFS::from_dir(&root)
+        .wrap_err_with(|| format!("failed to load content from {}", root.display()))
+}
+
 /// Main application state
 pub struct App {
     shell: Shell,
@@ -43,9 +85,9 @@ pub struct App {
 }
 
 impl App {
-    pub fn new() -> Self {
+    pub fn new(fs: VirtualFS) -> Self {
         Self {
-            shell: Shell::new(),
+            shell: Shell::new(fs),
             view: View::Shell,
             should_quit: false,
         }
@@ -54,6 +96,8 @@ impl App {
     pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
         // Show welcome sequence
         self.shell.show_welcome();
+        let size = terminal.size()?;
+        self.resize(size.width, size.height);
 
         while !self.should_quit {
             terminal.draw(|frame| self.draw(frame))?;
@@ -73,6 +117,8 @@ impl App {
             View::Resume(app) => app.render(frame),
             View::Contact(app) => app.render(frame),
             View::About(app) => app.render(frame),
+            View::Editor(app) => app.render(frame),
+            View::Pager(app) => app.render(frame),
         }
     }
 
@@ -87,8 +133,27 @@ impl App {
         self.shell.render(frame, inner);
     }
 
+    /// The shell sits inside a bordered block, so it gets two rows and
+    /// columns less
+    fn resize(&mut self, width: u16, height: u16) {
+        self.shell.resize(width.saturating_sub(2), height.saturating_sub(2));
+    }
+
     fn handle_events(&mut self) -> Result<()> {
-        if let Event::Key(key) = event::read()? {
+        let event = event::read()?;
+        if let Event::Resize(width, height) = event {
+            self.resize(width, height);
+        }
+        if let Event::Mouse(mouse) = event
+            && let View::Shell = self.view
+        {
+            match mouse.kind {
+                MouseEventKind::ScrollUp => self.shell.wheel_up(),
+                MouseEventKind::ScrollDown => self.shell.wheel_down(),
+                _ => {}
+            }
+        }
+        if let Event::Key(key) = event {
             if key.kind != KeyEventKind::Press {
                 return Ok(());
             }
@@ -102,23 +167,38 @@ impl App {
                         if let Some(app_name) = app.launch_app {
                             self.launch_app(app_name);
                         } else {
-                            self.view = View::Shell;
+                            self.return_to_shell();
                         }
                     }
                 }
                 View::Resume(app) => {
                     if app.handle_key(key) {
-                        self.view = View::Shell;
+                        self.return_to_shell();
                     }
                 }
                 View::Contact(app) => {
                     if app.handle_key(key) {
-                        self.view = View::Shell;
+                        self.return_to_shell();
                     }
                 }
                 View::About(app) => {
                     if app.handle_key(key) {
-                        self.view = View::Shell;
+                        self.return_to_shell();
+                    }
+                }
+                View::Pager(app) => {
+                    if app.handle_key(key) {
+                        self.return_to_shell();
+                    }
+                }
+                View::Editor(app) => {
+                    let exit = app.handle_key(key);
+                    if let Some((path, content)) = app.take_save() {
+                        let result = self.shell.save_file(&path, &content);
+                        app.save_finished(result);
+                    }
+                    if exit {
+                        self.return_to_shell();
                     }
                 }
             }
@@ -127,49 +207,39 @@ impl App {
     }
 
     fn handle_shell_events(&mut self, key: crossterm::event::KeyEvent) {
-        // Ctrl+C or Ctrl+D to quit
-        if key.modifiers.contains(KeyModifiers::CONTROL) {
-            match key.code {
-                KeyCode::Char('c') | KeyCode::Char('d') => {
-                    self.should_quit = true;
-                    return;
-                }
-                _ => {}
-            }
+        // Ctrl+C to quit; Ctrl+D quits from an empty line (handled by the shell)
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.should_quit = true;
+            return;
         }
 
-        match key.code {
-            KeyCode::Enter => match self.shell.submit() {
-                ShellResult::None => {}
-                ShellResult::Exit => {
-                    self.should_quit = true;
-                }
-                ShellResult::LaunchApp(app_name) => {
-                    self.launch_app(app_name);
-                }
-            },
-            KeyCode::Char(c) => {
-                self.shell.input(c);
-            }
-            KeyCode::Backspace => {
-                self.shell.backspace();
-            }
-            KeyCode::Left => {
-                self.shell.cursor_left();
-            }
-            KeyCode::Right => {
-                self.shell.cursor_right();
+        let result = self.shell.handle_key(key);
+        self.handle_shell_result(result);
+    }
+
+    /// Give the screen back to the shell, which may have more to show from
+    /// the command line that opened the view
+    fn return_to_shell(&mut self) {
+        self.view = View::Shell;
+        let result = self.shell.resume();
+        self.handle_shell_result(result);
+    }
+
+    fn handle_shell_result(&mut self, result: ShellResult) {
+        match result {
+            ShellResult::None => {}
+            ShellResult::Exit => {
+                self.should_quit = true;
             }
-            KeyCode::Up => {
-                self.shell.history_prev();
+            ShellResult::LaunchApp(app_name) => {
+                self.launch_app(app_name);
             }
-            KeyCode::Down => {
-                self.shell.history_next();
+            ShellResult::Edit { name, path, content } => {
+                self.view = View::Editor(EditorApp::new(name, path, &content));
             }
-            KeyCode::Tab => {
-                self.shell.tab_complete();
+            ShellResult::Page { title, lines } => {
+                self.view = View::Pager(PagerApp::new(title, lines));
             }
-            _ => {}
         }
     }
 
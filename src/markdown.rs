@@ -0,0 +1,480 @@
+//! Markdown to styled lines, for `glow` and the pager
+
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::highlight::{highlight, Language};
+use crate::theme::Theme;
+use crate::ui;
+
+/// Render `source` as styled lines. Paragraphs are word-wrapped to `width`
+/// (0 leaves them unwrapped). Links show their URL after the text: escape
+/// sequences like OSC 8 hyperlinks cannot go through ratatui's spans.
+pub fn render(source: &str, width: usize) -> Vec<Line<'static>> {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut renderer = Renderer::new(width);
+    for event in Parser::new_ext(source, options) {
+        renderer.event(event);
+    }
+    renderer.flush();
+    renderer.lines
+}
+
+fn code_style() -> Style {
+    Style::default().fg(Theme::WARNING)
+}
+
+fn link_style() -> Style {
+    Style::default()
+        .fg(Theme::HIGHLIGHT)
+        .add_modifier(Modifier::UNDERLINED)
+}
+
+/// An open list: the next number for ordered lists, and how far its items'
+/// continuation lines are indented
+struct List {
+    next: Option<u64>,
+    indent: usize,
+}
+
+/// A table being collected; it is laid out once every cell is known
+struct Table {
+    alignments: Vec<Alignment>,
+    rows: Vec<Vec<String>>,
+    /// Rows belonging to the header
+    head: usize,
+}
+
+struct Renderer {
+    width: usize,
+    lines: Vec<Line<'static>>,
+    /// Inline content of the block being built
+    spans: Vec<Span<'static>>,
+    /// Inline styles opened by emphasis, strong and so on
+    styles: Vec<Style>,
+    quote_depth: usize,
+    lists: Vec<List>,
+    /// Marker for the first line of the list item just opened
+    marker: Option<String>,
+    heading: Option<HeadingLevel>,
+    /// Fenced or indented code: its language and text so far
+    code: Option<(String, String)>,
+    /// URL of the open link or image, and where its text starts in `spans`
+    link: Option<(String, usize)>,
+    table: Option<Table>,
+    /// A block ended; the next one is separated by a blank line
+    gap: bool,
+}
+
+impl Renderer {
+    fn new(width: usize) -> Self {
+        Self {
+            width,
+            lines: Vec::new(),
+            spans: Vec::new(),
+            styles: Vec::new(),
+            quote_depth: 0,
+            lists: Vec::new(),
+            marker: None,
+            heading: None,
+            code: None,
+            link: None,
+            table: None,
+            gap: false,
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        if self.table.is_some() {
+            self.table_event(event);
+            return;
+        }
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => match &mut self.code {
+                Some((_, code)) => code.push_str(&text),
+                None => self.push(Span::styled(text.into_string(), self.style())),
+            },
+            Event::Code(code) => self.push(Span::styled(code.into_string(), code_style())),
+            Event::Html(html) => {
+                for line in html.lines() {
+                    self.push_line(vec![Span::styled(line.to_string(), Style::default().fg(Theme::MUTED))]);
+                }
+                self.gap = true;
+            }
+            Event::InlineHtml(html) => {
+                self.push(Span::styled(html.into_string(), Style::default().fg(Theme::MUTED)));
+            }
+            Event::SoftBreak => self.push(Span::styled(" ", self.style())),
+            Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.separate();
+                let width = match self.width {
+                    0 => 40,
+                    width => width.saturating_sub(self.prefix_width()),
+                };
+                self.push_line(vec![Span::styled("─".repeat(width), Style::default().fg(Theme::MUTED))]);
+                self.gap = true;
+            }
+            Event::TaskListMarker(done) => {
+                let marker = if done { "[x] " } else { "[ ] " };
+                self.push(Span::styled(marker, Style::default().fg(Theme::ACCENT)));
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.separate(),
+            Tag::Heading { level, .. } => {
+                self.separate();
+                self.heading = Some(level);
+            }
+            Tag::BlockQuote(_) => {
+                self.separate();
+                self.quote_depth += 1;
+            }
+            Tag::CodeBlock(kind) => {
+                self.separate();
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info.split_whitespace().next().unwrap_or("").to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code = Some((language, String::new()));
+            }
+            Tag::List(start) => {
+                // A nested list continues its parent item without a gap
+                if self.lists.is_empty() {
+                    self.separate();
+                } else {
+                    self.flush();
+                }
+                self.lists.push(List { next: start, indent: 2 });
+            }
+            Tag::Item => {
+                self.flush();
+                if let Some(list) = self.lists.last_mut() {
+                    let marker = match &mut list.next {
+                        Some(n) => {
+                            *n += 1;
+                            format!("{}. ", *n - 1)
+                        }
+                        None => "• ".to_string(),
+                    };
+                    list.indent = marker.width();
+                    self.marker = Some(marker);
+                }
+            }
+            Tag::Table(alignments) => {
+                self.separate();
+                self.table = Some(Table {
+                    alignments,
+                    rows: Vec::new(),
+                    head: 0,
+                });
+            }
+            Tag::Emphasis => self.styles.push(Style::default().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.styles.push(Style::default().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => self.styles.push(Style::default().add_modifier(Modifier::CROSSED_OUT)),
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.begin_line();
+                self.link = Some((dest_url.into_string(), self.spans.len()));
+                self.styles.push(link_style());
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => {
+                self.flush();
+                self.gap = true;
+            }
+            TagEnd::Heading(level) => {
+                self.heading = None;
+                let text: String = self.spans.drain(..).map(|span| span.content.into_owned()).collect();
+                let text = text.trim_start();
+                let line = match level {
+                    HeadingLevel::H1 => ui::heading(text),
+                    HeadingLevel::H2 => ui::subheading(text),
+                    _ => Line::from(Span::styled(
+                        text.to_string(),
+                        Style::default().fg(Theme::FOREGROUND).add_modifier(Modifier::BOLD),
+                    )),
+                };
+                self.push_line(line.spans);
+                self.gap = true;
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.quote_depth -= 1;
+                self.gap = true;
+            }
+            TagEnd::CodeBlock => {
+                if let Some((language, code)) = self.code.take() {
+                    let lines = match Language::from_name(&language) {
+                        Some(language) => highlight(&code, language),
+                        None => code
+                            .lines()
+                            .map(|line| Line::from(Span::styled(line.to_string(), code_style())))
+                            .collect(),
+                    };
+                    for line in lines {
+                        let mut spans = vec![Span::raw("  ")];
+                        spans.extend(line.spans);
+                        self.push_line(spans);
+                    }
+                }
+                self.gap = true;
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.gap = true;
+                }
+            }
+            TagEnd::Item => {
+                self.flush();
+                self.marker = None;
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link | TagEnd::Image => {
+                self.styles.pop();
+                self.end_link(tag == TagEnd::Image);
+            }
+            _ => {}
+        }
+    }
+
+    /// Collapse the link's text into one span, followed by its URL
+    fn end_link(&mut self, image: bool) {
+        let Some((url, start)) = self.link.take() else {
+            return;
+        };
+        let start = start.min(self.spans.len());
+        let mut text: String = self.spans.drain(start..).map(|span| span.content.into_owned()).collect();
+        if image {
+            text = format!("[image: {}]", text);
+        } else if text.is_empty() {
+            text = url.clone();
+        }
+
+        let shows_url = text == url || url.strip_prefix("mailto:") == Some(&text);
+        self.spans.push(Span::styled(text, self.style().patch(link_style())));
+        if !shows_url {
+            self.spans.push(Span::styled(format!(" ({})", url), Style::default().fg(Theme::MUTED)));
+        }
+    }
+
+    /// Collect table cells as plain text
+    fn table_event(&mut self, event: Event) {
+        let Some(table) = &mut self.table else {
+            return;
+        };
+        match event {
+            Event::Start(Tag::TableHead) | Event::Start(Tag::TableRow) => table.rows.push(Vec::new()),
+            Event::End(TagEnd::TableHead) => table.head = table.rows.len(),
+            Event::Start(Tag::TableCell) => {
+                if let Some(row) = table.rows.last_mut() {
+                    row.push(String::new());
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(cell) = table.rows.last_mut().and_then(|row| row.last_mut()) {
+                    cell.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Table) => {
+                if let Some(table) = self.table.take() {
+                    self.render_table(table);
+                }
+                self.gap = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn render_table(&mut self, table: Table) {
+        let columns = table.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let widths: Vec<usize> = (0..columns)
+            .map(|c| {
+                table
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(c))
+                    .map(|cell| cell.width())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let border = Style::default().fg(Theme::MUTED);
+
+        for (r, row) in table.rows.iter().enumerate() {
+            let style = if r < table.head {
+                Style::default().fg(Theme::ACCENT).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let mut spans = Vec::new();
+            for (c, width) in widths.iter().enumerate() {
+                if c > 0 {
+                    spans.push(Span::styled(" │ ", border));
+                }
+                let cell = row.get(c).map(String::as_str).unwrap_or("");
+                let pad = width - cell.width();
+                let (left, right) = match table.alignments.get(c) {
+                    Some(Alignment::Right) => (pad, 0),
+                    Some(Alignment::Center) => (pad / 2, pad - pad / 2),
+                    _ => (0, pad),
+                };
+                spans.push(Span::styled(
+                    format!("{}{}{}", " ".repeat(left), cell, " ".repeat(right)),
+                    style,
+                ));
+            }
+            self.push_line(spans);
+
+            if r + 1 == table.head {
+                let rule: Vec<String> = widths.iter().map(|w| "─".repeat(*w)).collect();
+                self.push_line(vec![Span::styled(rule.join("─┼─"), border)]);
+            }
+        }
+    }
+
+    /// The inline style in effect
+    fn style(&self) -> Style {
+        self.styles
+            .iter()
+            .fold(Style::default(), |style, inner| style.patch(*inner))
+    }
+
+    /// Blank line between blocks, once there is something to separate
+    fn separate(&mut self) {
+        self.flush();
+        if self.gap && !self.lines.is_empty() {
+            let bars = self.quote_bars();
+            self.lines.push(Line::from(bars));
+        }
+        self.gap = false;
+    }
+
+    fn quote_bars(&self) -> Vec<Span<'static>> {
+        (0..self.quote_depth)
+            .map(|_| Span::styled("│ ", Style::default().fg(Theme::MUTED)))
+            .collect()
+    }
+
+    /// Quote bars and list indentation for the next line. The first line of
+    /// a list item gets its marker; the rest line up under the text.
+    fn prefix(&mut self, first: bool) -> Vec<Span<'static>> {
+        let mut spans = self.quote_bars();
+        let depth = self.lists.len();
+        for (i, list) in self.lists.iter().enumerate() {
+            match &self.marker {
+                Some(marker) if first && i + 1 == depth => {
+                    spans.push(Span::styled(marker.clone(), Style::default().fg(Theme::ACCENT)));
+                }
+                _ => spans.push(Span::raw(" ".repeat(list.indent))),
+            }
+        }
+        if first {
+            self.marker = None;
+        }
+        spans
+    }
+
+    fn prefix_width(&self) -> usize {
+        2 * self.quote_depth + self.lists.iter().map(|list| list.indent).sum::<usize>()
+    }
+
+    /// Mark the start of inline content. Nothing is emitted until the line
+    /// is flushed, but the gap before the block is settled now.
+    fn begin_line(&mut self) {
+        if self.spans.is_empty() && self.gap {
+            self.separate();
+        }
+    }
+
+    fn push(&mut self, span: Span<'static>) {
+        self.begin_line();
+        self.spans.push(span);
+    }
+
+    /// Emit one line of a block that is already laid out
+    fn push_line(&mut self, spans: Vec<Span<'static>>) {
+        self.separate();
+        let mut line = self.prefix(true);
+        line.extend(spans);
+        self.lines.push(Line::from(line));
+    }
+
+    /// Word-wrap the pending inline content into lines
+    fn flush(&mut self) {
+        if self.spans.is_empty() || self.heading.is_some() {
+            return;
+        }
+        let spans = std::mem::take(&mut self.spans);
+        let width = self.width.saturating_sub(self.prefix_width());
+        let mut first = true;
+        for row in wrap_words(spans, if self.width == 0 { 0 } else { width.max(10) }) {
+            let mut line = self.prefix(first);
+            line.extend(row);
+            self.lines.push(Line::from(line));
+            first = false;
+        }
+    }
+}
+
+/// Greedy word wrap that keeps each word's style
+fn wrap_words(spans: Vec<Span<'static>>, width: usize) -> Vec<Vec<Span<'static>>> {
+    if width == 0 {
+        return vec![spans];
+    }
+
+    // Words with the whitespace that follows them
+    let mut words: Vec<(Span<'static>, bool)> = Vec::new();
+    for span in spans {
+        for piece in span.content.split_inclusive(' ') {
+            let word = piece.trim_end_matches(' ');
+            if !word.is_empty() {
+                words.push((Span::styled(word.to_string(), span.style), false));
+            }
+            if piece.ends_with(' ')
+                && let Some((_, space)) = words.last_mut()
+            {
+                *space = true;
+            }
+        }
+    }
+
+    let mut rows = Vec::new();
+    let mut row: Vec<Span<'static>> = Vec::new();
+    let mut col = 0;
+    let mut space_before = false;
+    for (word, space_after) in words {
+        let w = word.content.width();
+        let gap = usize::from(space_before && col > 0);
+        if col > 0 && col + gap + w > width {
+            rows.push(std::mem::take(&mut row));
+            col = 0;
+        } else if gap == 1 {
+            row.push(Span::raw(" "));
+            col += 1;
+        }
+        col += w;
+        row.push(word);
+        space_before = space_after;
+    }
+    rows.push(row);
+    rows
+}
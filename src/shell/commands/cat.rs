@@ -0,0 +1,383 @@
+//! `cat`, `less`, `glow` and `bat` - print, page, render or highlight
+//! files, or what comes in on stdin
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use super::{error, split_flags, Command, CommandResult, Context};
+use crate::highlight::{highlight, Language};
+use crate::markdown;
+use crate::shell::env::Environment;
+use crate::shell::{FSEntry, VirtualFS};
+use crate::theme::Theme;
+use crate::ui;
+
+pub struct Cat;
+
+impl Command for Cat {
+    fn name(&self) -> &'static str {
+        "cat"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "cat [file...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Display file contents"
+    }
+
+    fn help(&self) -> &'static str {
+        "Print each file in turn. Symlinks are followed. Source files are \
+         syntax highlighted on the terminal. With no files, print the \
+         output of the previous pipeline stage."
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("cat README.md", "print the README"),
+            ("cat .profile .secret > both", "join two files into a new one"),
+        ]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let format = |arg: &str, content: &str| {
+            if ctx.tty { highlighted(arg, content) } else { raw(content) }
+        };
+        concatenate("cat", args, ctx.stdin.take(), ctx.fs, ctx.cwd, &format)
+    }
+}
+
+pub struct Less;
+
+impl Command for Less {
+    fn name(&self) -> &'static str {
+        "less"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["more"]
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "less [file...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Page through files"
+    }
+
+    fn help(&self) -> &'static str {
+        "Show files, or the previous pipeline stage's output, a screen at \
+         a time. Markdown files are rendered as with glow. When less is not \
+         the last stage, it acts like cat.\n\n\
+         j k      line down / up\n\
+         Space b  page down / up\n\
+         d u      half page down / up\n\
+         g G      first / last line\n\
+         /pat     search forward; ?pat searches backward\n\
+         n N      next / previous match\n\
+         -N       toggle line numbers\n\
+         q        quit"
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("less blog/2026/building-portfolio-in-rust.md", "read a blog post"),
+            ("history | less", "page through the history"),
+        ]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let title = if args.is_empty() { "(stdin)".to_string() } else { args.join(" ") };
+        if !ctx.tty {
+            return concatenate("less", args, ctx.stdin.take(), ctx.fs, ctx.cwd, &|_, content| raw(content));
+        }
+        let width = columns(ctx.env);
+        let format = |path: &str, content: &str| {
+            if is_markdown(path) {
+                markdown::render(content, width)
+            } else {
+                raw(content)
+            }
+        };
+        match concatenate("less", args, ctx.stdin.take(), ctx.fs, ctx.cwd, &format) {
+            CommandResult::Output { stdout, stderr, .. } if stderr.is_empty() => CommandResult::Page {
+                title,
+                lines: stdout,
+            },
+            other => other,
+        }
+    }
+}
+
+pub struct Glow;
+
+impl Command for Glow {
+    fn name(&self) -> &'static str {
+        "glow"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["mdcat"]
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "glow [-p] [file...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Render markdown files"
+    }
+
+    fn help(&self) -> &'static str {
+        "Print markdown with headings, emphasis, code, lists, quotes and \
+         tables styled, and each link's address after its text. With no \
+         files, render the previous pipeline stage's output."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-p", "show the result in the pager")]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[("glow README.md", "the README, formatted")]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let (flags, files) = split_flags(args);
+        let mut page = false;
+        for flag in flags {
+            match flag {
+                'p' => page = true,
+                other => return error(&format!("glow: invalid option -- '{}'", other)),
+            }
+        }
+
+        // Pipes get unwrapped paragraphs
+        let width = if ctx.tty { columns(ctx.env) } else { 0 };
+        let render = |_: &str, content: &str| markdown::render(content, width);
+        let result = match ctx.stdin.take() {
+            Some(stdin) if files.is_empty() => {
+                let source: Vec<String> = stdin.iter().map(ui::line_to_string).collect();
+                CommandResult::output(render("", &source.join("\n")))
+            }
+            stdin => concatenate("glow", &files, stdin, ctx.fs, ctx.cwd, &render),
+        };
+        match result {
+            CommandResult::Output { stdout, stderr, .. } if page && ctx.tty && stderr.is_empty() => {
+                let title = if files.is_empty() { "(stdin)".to_string() } else { files.join(" ") };
+                CommandResult::Page { title, lines: stdout }
+            }
+            other => other,
+        }
+    }
+}
+
+pub struct Bat;
+
+impl Command for Bat {
+    fn name(&self) -> &'static str {
+        "bat"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["batcat"]
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "bat [-n|-p] [-l language] [file...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Show files with syntax highlighting"
+    }
+
+    fn help(&self) -> &'static str {
+        "Print files syntax highlighted, under a header with the file name \
+         and beside line numbers. The language comes from the file name \
+         unless -l names one. When bat is not the last stage, it acts like \
+         cat."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-n", "line numbers only, no header"),
+            ("-p", "no decorations at all"),
+            ("-l language", "rust, python, js, ts, sh, toml or json"),
+        ]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("bat .profile", "the profile, highlighted as shell"),
+            ("echo '{\"a\": 1}' | bat -l json", "highlight piped text"),
+        ]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let mut style = BatStyle::Full;
+        let mut language = None;
+        let mut files = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match *arg {
+                "-n" | "--number" => style = BatStyle::Numbers,
+                "-p" | "--plain" => style = BatStyle::Plain,
+                "-l" | "--language" => {
+                    let Some(name) = args.next() else {
+                        return error(&format!("bat: option '{}' requires an argument", arg));
+                    };
+                    match Language::from_name(name) {
+                        Some(found) => language = Some(found),
+                        None => return error(&format!("bat: unknown language '{}'", name)),
+                    }
+                }
+                flag if flag.starts_with('-') && flag != "-" => {
+                    return error(&format!("bat: unknown option '{}'", flag));
+                }
+                file => files.push(file),
+            }
+        }
+
+        // Like the real bat, plain text when the output is not the terminal
+        if !ctx.tty {
+            return concatenate("bat", &files, ctx.stdin.take(), ctx.fs, ctx.cwd, &|_, content| raw(content));
+        }
+
+        let width = columns(ctx.env);
+        let format = |name: &str, content: &str| {
+            let lines = match language.or_else(|| Language::from_path(name)) {
+                Some(language) => highlight(content, language),
+                None => raw(content),
+            };
+            decorate(name, lines, style, width)
+        };
+        match ctx.stdin.take() {
+            Some(stdin) if files.is_empty() => {
+                let source: Vec<String> = stdin.iter().map(ui::line_to_string).collect();
+                CommandResult::output(format("STDIN", &source.join("\n")))
+            }
+            stdin => concatenate("bat", &files, stdin, ctx.fs, ctx.cwd, &format),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum BatStyle {
+    /// Header, grid and line numbers
+    Full,
+    Numbers,
+    Plain,
+}
+
+/// Width of bat's line number gutter, up to the grid line
+const GUTTER: usize = 7;
+
+/// Frame highlighted lines the way `bat` does
+fn decorate(name: &str, lines: Vec<Line<'static>>, style: BatStyle, width: usize) -> Vec<Line<'static>> {
+    let muted = Style::default().fg(Theme::MUTED);
+    let number = |n: usize, width: usize, grid: &str| Span::styled(format!("{:>width$}{}", n, grid), muted);
+    let rule = |joint: &str| {
+        let rest = width.saturating_sub(GUTTER + 1).max(1);
+        Line::from(Span::styled(format!("{}{}{}", "─".repeat(GUTTER), joint, "─".repeat(rest)), muted))
+    };
+
+    match style {
+        BatStyle::Plain => lines,
+        BatStyle::Numbers => lines
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let mut spans = vec![number(i + 1, 4, " ")];
+                spans.extend(line.spans);
+                Line::from(spans)
+            })
+            .collect(),
+        BatStyle::Full => {
+            let mut out = vec![
+                rule("┬"),
+                Line::from(vec![
+                    Span::styled(format!("{}│ File: ", " ".repeat(GUTTER)), muted),
+                    Span::styled(name.to_string(), Style::default().add_modifier(Modifier::BOLD)),
+                ]),
+                rule("┼"),
+            ];
+            for (i, line) in lines.into_iter().enumerate() {
+                let mut spans = vec![number(i + 1, GUTTER - 1, " │ ")];
+                spans.extend(line.spans);
+                out.push(Line::from(spans));
+            }
+            out.push(rule("┴"));
+            out
+        }
+    }
+}
+
+/// File contents highlighted for the language its name suggests
+fn highlighted(name: &str, content: &str) -> Vec<Line<'static>> {
+    match Language::from_path(name) {
+        Some(language) => highlight(content, language),
+        None => raw(content),
+    }
+}
+
+fn is_markdown(path: &str) -> bool {
+    path.ends_with(".md") || path.ends_with(".markdown")
+}
+
+/// Width to lay out for: the terminal's, as exported in `$COLUMNS`
+pub fn columns(env: &Environment) -> usize {
+    env.get("COLUMNS").and_then(|c| c.parse().ok()).unwrap_or(80)
+}
+
+/// File contents line by line, unstyled
+fn raw(content: &str) -> Vec<Line<'static>> {
+    content.lines().map(|line| Line::from(line.to_string())).collect()
+}
+
+fn concatenate(
+    name: &str,
+    args: &[&str],
+    stdin: Option<Vec<Line<'static>>>,
+    fs: &VirtualFS,
+    cwd: &str,
+    format: &dyn Fn(&str, &str) -> Vec<Line<'static>>,
+) -> CommandResult {
+    if args.is_empty() {
+        return match stdin {
+            Some(lines) => CommandResult::output(lines),
+            None => error(&format!("{}: missing operand", name)),
+        };
+    }
+
+    let mut lines = Vec::new();
+    let mut errors = Vec::new();
+
+    for arg in args {
+        let path = fs.resolve_path(arg, cwd);
+        match fs.stat(&path) {
+            Ok(FSEntry::File { content, .. }) => {
+                lines.extend(format(arg, content));
+            }
+            Ok(FSEntry::Directory { .. }) => {
+                errors.push(ui::error_text(&format!("{}: {}: Is a directory", name, arg)));
+            }
+            Ok(FSEntry::Binary { .. }) => {
+                errors.push(ui::error_text(&format!("{}: {}: binary file", name, arg)));
+            }
+            Ok(FSEntry::Executable { .. }) => {
+                errors.push(Line::from(Span::styled(
+                    format!("{}: {}: Is an executable (try running it with ./{})", name, arg, arg),
+                    Style::default().fg(Theme::WARNING),
+                )));
+            }
+            Ok(FSEntry::Symlink { .. }) => unreachable!("stat follows symlinks"),
+            Err(reason) => errors.push(ui::error_text(&format!("{}: {}: {}", name, arg, reason))),
+        }
+    }
+
+    CommandResult::new(lines, errors)
+}
@@ -0,0 +1,476 @@
+//! Easter eggs. All hidden from `help`; `eastereggs` lists them.
+
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+
+use super::{all, ArgKind, Command, CommandResult, Context};
+use crate::theme::Theme;
+use crate::ui;
+
+pub struct Eastereggs;
+
+impl Command for Eastereggs {
+    fn name(&self) -> &'static str {
+        "eastereggs"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "eastereggs"
+    }
+
+    fn summary(&self) -> &'static str {
+        "List the easter eggs"
+    }
+
+    fn hidden(&self) -> bool {
+        true
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, _args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::output(list())
+    }
+}
+
+/// Every hidden command, plus the eggs that are files rather than commands
+fn list() -> Vec<Line<'static>> {
+    let mut entries = vec![("cat .secret", "Find a hidden secret")];
+    entries.extend(
+        all()
+            .iter()
+            .filter(|command| command.hidden() && command.name() != "eastereggs")
+            .map(|command| (command.synopsis(), command.summary())),
+    );
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled("🥚 Easter Eggs 🥚", Style::default().fg(Theme::WARNING))),
+        Line::from(""),
+        Line::from("Try some of these hidden commands:"),
+        Line::from(""),
+    ];
+    for (synopsis, summary) in entries {
+        lines.push(Line::from(format!("  • {:<20} - {}", synopsis, summary)));
+    }
+    lines.extend([
+        Line::from(""),
+        Line::from("More surprises await... keep exploring! 🔍"),
+        Line::from(""),
+    ]);
+    lines
+}
+
+pub struct Sudo;
+
+impl Command for Sudo {
+    fn name(&self) -> &'static str {
+        "sudo"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "sudo rm -rf /"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Nice try..."
+    }
+
+    fn hidden(&self) -> bool {
+        true
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Commands
+    }
+
+    fn run(&self, args: &[&str], _ctx: &mut Context) -> CommandResult {
+        sudo(args)
+    }
+}
+
+pub struct Hack;
+
+impl Command for Hack {
+    fn name(&self) -> &'static str {
+        "hack"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "hack"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Hack the mainframe"
+    }
+
+    fn hidden(&self) -> bool {
+        true
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, _args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::output(hack())
+    }
+}
+
+pub struct Vim;
+
+impl Command for Vim {
+    fn name(&self) -> &'static str {
+        "vim"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["nvim"]
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "vim"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Editor wars"
+    }
+
+    fn hidden(&self) -> bool {
+        true
+    }
+
+    fn run(&self, _args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::output(vim())
+    }
+}
+
+pub struct Emacs;
+
+impl Command for Emacs {
+    fn name(&self) -> &'static str {
+        "emacs"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "emacs"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Editor wars, the other side"
+    }
+
+    fn hidden(&self) -> bool {
+        true
+    }
+
+    fn run(&self, _args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::output(emacs())
+    }
+}
+
+pub struct Neofetch;
+
+impl Command for Neofetch {
+    fn name(&self) -> &'static str {
+        "neofetch"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "neofetch"
+    }
+
+    fn summary(&self) -> &'static str {
+        "System info"
+    }
+
+    fn hidden(&self) -> bool {
+        true
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, _args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::output(neofetch())
+    }
+}
+
+pub struct Cowsay;
+
+impl Command for Cowsay {
+    fn name(&self) -> &'static str {
+        "cowsay"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "cowsay <message>"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Moo!"
+    }
+
+    fn hidden(&self) -> bool {
+        true
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        CommandResult::output(cowsay(args, ctx.stdin.take()))
+    }
+}
+
+pub struct Fortune;
+
+impl Command for Fortune {
+    fn name(&self) -> &'static str {
+        "fortune"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "fortune"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Get your fortune"
+    }
+
+    fn hidden(&self) -> bool {
+        true
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, _args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::output(fortune())
+    }
+}
+
+pub struct Matrix;
+
+impl Command for Matrix {
+    fn name(&self) -> &'static str {
+        "matrix"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "matrix"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Enter the matrix"
+    }
+
+    fn hidden(&self) -> bool {
+        true
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, _args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::output(matrix())
+    }
+}
+
+pub struct Sl;
+
+impl Command for Sl {
+    fn name(&self) -> &'static str {
+        "sl"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "sl"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Missing train?"
+    }
+
+    fn hidden(&self) -> bool {
+        true
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, _args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::output(sl())
+    }
+}
+
+fn sudo(args: &[&str]) -> CommandResult {
+    if args.join(" ").contains("rm -rf") {
+        CommandResult::output(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "Nice try! This is a sandboxed environment 😉",
+                Style::default().fg(Theme::WARNING),
+            )),
+            Line::from(""),
+        ])
+    } else {
+        CommandResult::failed(vec![Line::from(Span::styled(
+            "guest is not in the sudoers file. This incident will be reported.",
+            Style::default().fg(Theme::ERROR),
+        ))])
+    }
+}
+
+fn hack() -> Vec<Line<'static>> {
+    vec![
+        Line::from(""),
+        Line::from("   ACCESSING MAINFRAME..."),
+        Line::from("   [████████████████████] 100%"),
+        Line::from(""),
+        Line::from(Span::styled(
+            "   Just kidding! But I like your style 😎",
+            Style::default().fg(Theme::SUCCESS),
+        )),
+        Line::from(""),
+    ]
+}
+
+fn vim() -> Vec<Line<'static>> {
+    vec![
+        Line::from(""),
+        Line::from("   VIM - Vi IMproved"),
+        Line::from("   version 8.2.0"),
+        Line::from(""),
+        Line::from(Span::styled(
+            "   BTW, I use Neovim 💚",
+            Style::default().fg(Theme::SUCCESS),
+        )),
+        Line::from(""),
+    ]
+}
+
+fn emacs() -> Vec<Line<'static>> {
+    vec![
+        Line::from(""),
+        Line::from("   GNU Emacs 29.1"),
+        Line::from("   Copyright (C) 2024 Free Software Foundation"),
+        Line::from(""),
+        Line::from(Span::styled(
+            "   Real programmers use ed. Or maybe vim. 🔥",
+            Style::default().fg(Theme::WARNING),
+        )),
+        Line::from(""),
+    ]
+}
+
+fn neofetch() -> Vec<Line<'static>> {
+    vec![
+        Line::from(""),
+        Line::from(Span::styled("       _,met$$$$$gg.          ", Style::default().fg(Theme::SECONDARY))),
+        Line::from(Span::styled("    ,g$$$$$$$$$$$$$$$P.       ", Style::default().fg(Theme::SECONDARY))),
+        Line::from(Span::styled("  ,g$$P\"     \"\"\"Y$$.\".        ", Style::default().fg(Theme::SECONDARY))),
+        Line::from(Span::styled(" ,$$P'              `$$$.     ", Style::default().fg(Theme::SECONDARY))),
+        Line::from(Span::styled("',$$P       ,ggs.     `$$b:   guest@uchindami", Style::default().fg(Theme::SECONDARY))),
+        Line::from(Span::styled("`d$$'     ,$P\"'   .    $$$    ----------------", Style::default().fg(Theme::SECONDARY))),
+        Line::from(Span::styled(" $$P      d$'     ,    $$P    OS: Portfolio OS v2.0.26", Style::default().fg(Theme::SECONDARY))),
+        Line::from(Span::styled(" $$:      $$.   -    ,d$$'    Kernel: Rust 1.83.0", Style::default().fg(Theme::SECONDARY))),
+        Line::from(Span::styled(" $$;      Y$b._   _,d$P'      Shell: rsh", Style::default().fg(Theme::SECONDARY))),
+        Line::from(Span::styled(" Y$$.    `.`\"Y$$$$P\"'         Terminal: ttyd", Style::default().fg(Theme::SECONDARY))),
+        Line::from(Span::styled(" `$$b      \"-.__              Framework: Ratatui", Style::default().fg(Theme::SECONDARY))),
+        Line::from(""),
+    ]
+}
+
+fn cowsay(args: &[&str], stdin: Option<Vec<Line<'static>>>) -> Vec<Line<'static>> {
+    let piped = stdin
+        .map(|lines| lines.iter().map(ui::line_to_string).collect::<Vec<_>>().join(" "))
+        .unwrap_or_default();
+    let msg = if !args.is_empty() {
+        &args.join(" ")
+    } else if !piped.trim().is_empty() {
+        piped.trim()
+    } else {
+        "Moo!"
+    };
+    let border = "-".repeat(msg.len() + 2);
+    vec![
+        Line::from(format!(" {}", border)),
+        Line::from(format!("< {} >", msg)),
+        Line::from(format!(" {}", border)),
+        Line::from("        \\   ^__^"),
+        Line::from("         \\  (oo)\\_______"),
+        Line::from("            (__)\\       )\\/\\"),
+        Line::from("                ||----w |"),
+        Line::from("                ||     ||"),
+    ]
+}
+
+fn fortune() -> Vec<Line<'static>> {
+    let fortunes = [
+        "A bug in the code is worth two in documentation.",
+        "Your code will compile on the first try today. (Just kidding)",
+        "The best time to document your code was yesterday. The second best is never.",
+        "Today you will discover a missing semicolon that took 3 hours to find.",
+        "A commit a day keeps the merge conflicts away.",
+        "You will refactor that function 'later'. You won't.",
+        "The cloud is just someone else's computer.",
+        "There are only 10 types of people: those who understand binary and those who don't.",
+    ];
+    // Use a simple "random" based on current line count
+    let idx = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as usize % fortunes.len())
+        .unwrap_or(0);
+    vec![
+        Line::from(""),
+        Line::from(Span::styled("🔮 Your fortune:", Style::default().fg(Theme::SECONDARY))),
+        Line::from(""),
+        Line::from(format!("   {}", fortunes[idx])),
+        Line::from(""),
+    ]
+}
+
+fn matrix() -> Vec<Line<'static>> {
+    vec![
+        Line::from(""),
+        Line::from(Span::styled("   ░▒▓█ THE MATRIX █▓▒░", Style::default().fg(Theme::SUCCESS))),
+        Line::from(""),
+        Line::from(Span::styled("   Wake up, Neo...", Style::default().fg(Theme::SUCCESS))),
+        Line::from(Span::styled("   The Matrix has you...", Style::default().fg(Theme::SUCCESS))),
+        Line::from(Span::styled("   Follow the white rabbit.", Style::default().fg(Theme::SUCCESS))),
+        Line::from(""),
+        Line::from("   01001000 01100101 01101100 01101100 01101111"),
+        Line::from(""),
+    ]
+}
+
+fn sl() -> Vec<Line<'static>> {
+    vec![
+        Line::from(""),
+        Line::from("                          (  ) (@@) ( )  (@)  ()    @@    O     @     O"),
+        Line::from("                     (@@@)"),
+        Line::from("                 (    )"),
+        Line::from("              (@@@@)"),
+        Line::from("            (   )"),
+        Line::from("        ====        ________                ___________"),
+        Line::from("    _D _|  |_______/        \\__I_I_____===__|_________|"),
+        Line::from("     |(_)---  |   H\\________/ |   |        =|___ ___|"),
+        Line::from("     /     |  |   H  |  |     |   |         ||_| |_||"),
+        Line::from("    |      |  |   H  |__--------------------| [___] |"),
+        Line::from("    | ________|___H__/__|_____/[][]~\\_______|       |"),
+        Line::from("    |/ |   |-----------I_____I [][] []  D   |=======|"),
+        Line::from(""),
+        Line::from(Span::styled("   🚂 You've been hit by a smooth criminal train!", Style::default().fg(Theme::WARNING))),
+        Line::from(""),
+    ]
+}
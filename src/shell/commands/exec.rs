@@ -0,0 +1,717 @@
+//! Executor - runs parsed command lines and scripts against the builtins
+
+use super::info::short_usage;
+use super::{error, find, CommandResult, Context};
+use crate::shell::env::Environment;
+use crate::shell::expand::{expand_assignment, expand_word, expand_words};
+use crate::shell::filesystem::{child_path, HOME};
+use crate::shell::history::History;
+use crate::shell::lexer::Word;
+use crate::shell::parser::{
+    Command, CommandList, Connector, Pipeline, Redirect, RedirectTarget, Script, SimpleCommand,
+};
+use crate::shell::{FSEntry, VirtualFS};
+use crate::ui;
+
+/// Executables with a sub-app behind them
+const APPS: &[&str] = &["about", "contact", "dashboard", "resume"];
+
+/// Passes a `while` or `until` loop may make before it is stopped, so a
+/// loop that never ends cannot hang the terminal
+const MAX_ITERATIONS: usize = 10_000;
+
+/// Exit status of a command line that does not parse, as in bash
+const SYNTAX_ERROR_STATUS: i32 = 2;
+
+/// Exit statuses for a command that is not found, or found but cannot run
+const NOT_FOUND_STATUS: i32 = 127;
+const NOT_EXECUTABLE_STATUS: i32 = 126;
+
+/// Redirecting here throws the output away
+const DEV_NULL: &str = "/dev/null";
+
+/// Execute a command line, or a whole script, and return its results in
+/// the order they happened. A syntax error stops it; whatever came before
+/// has already run.
+pub fn execute_command(
+    cmd: &str,
+    fs: &mut VirtualFS,
+    cwd: &mut String,
+    env: &mut Environment,
+    history: &mut History,
+) -> Vec<CommandResult> {
+    let mut script = match Script::new(cmd) {
+        Ok(script) => script,
+        Err(msg) => {
+            env.set_status(SYNTAX_ERROR_STATUS);
+            return vec![error(&msg).with_status(SYNTAX_ERROR_STATUS)];
+        }
+    };
+
+    let mut results = Vec::new();
+    while let Some(list) = script.next_command(env) {
+        match list {
+            Ok(list) => results.extend(run_list(&list, fs, cwd, env, history)),
+            Err(msg) => {
+                env.set_status(SYNTAX_ERROR_STATUS);
+                results.push(error(&msg).with_status(SYNTAX_ERROR_STATUS));
+                break;
+            }
+        }
+        if exits(results.last()) {
+            break;
+        }
+    }
+    results
+}
+
+/// `exit` ends the session or script, so nothing after it runs. The
+/// pager, editor and apps do not stop the line: the commands after them
+/// run straight away, and the shell shows what they print once the
+/// screen is handed back.
+fn exits(result: Option<&CommandResult>) -> bool {
+    match result {
+        Some(CommandResult::Sequence(results)) => exits(results.last()),
+        other => matches!(other, Some(CommandResult::Exit(_))),
+    }
+}
+
+/// Run the pipelines of a parsed command line, honouring `&&` and `||`
+/// and keeping `$?` up to date
+fn run_list(
+    list: &CommandList,
+    fs: &mut VirtualFS,
+    cwd: &mut String,
+    env: &mut Environment,
+    history: &mut History,
+) -> Vec<CommandResult> {
+    let mut results = Vec::new();
+    let mut success = true;
+
+    for (connector, pipeline) in &list.items {
+        let should_run = match connector {
+            Connector::Seq => true,
+            Connector::And => success,
+            Connector::Or => !success,
+        };
+        if !should_run {
+            continue;
+        }
+
+        let status = run_pipeline(pipeline, fs, cwd, env, history, &mut results);
+        env.set_status(status);
+        success = status == 0;
+        if exits(results.last()) {
+            break;
+        }
+    }
+
+    results
+}
+
+/// Run each stage with the previous stage's output as stdin.
+/// Returns the exit status of the last stage.
+fn run_pipeline(
+    pipeline: &Pipeline,
+    fs: &mut VirtualFS,
+    cwd: &mut String,
+    env: &mut Environment,
+    history: &mut History,
+    results: &mut Vec<CommandResult>,
+) -> i32 {
+    let mut stdin = None;
+    let last = pipeline.commands.len() - 1;
+
+    for (i, command) in pipeline.commands.iter().enumerate() {
+        let redirects = command.redirects();
+        let command = match command {
+            Command::Redirected { command, .. } => &**command,
+            command => command,
+        };
+        // Only the last stage writes to the terminal, unless its stdout is redirected
+        let tty = i == last && !redirects.iter().any(|redirect| redirect.fd == 1);
+        // Redirections are set up first, so a command that cannot have its
+        // output put where it was asked does not run at all
+        let opened = open_redirects(redirects, fs, cwd, env);
+        let mut ctx = Context {
+            fs,
+            cwd,
+            env,
+            history,
+            stdin: stdin.take(),
+            tty,
+        };
+        let result = match (command, opened) {
+            (_, Err(e)) => e,
+            (Command::Simple(simple), Ok(redirections)) => {
+                let result = run_simple_command(simple, &mut ctx);
+                redirections.apply(result, fs)
+            }
+            // The last stage's results go straight out, so that what it
+            // printed before an `exit` is kept
+            (compound, Ok(_)) if i == last && redirects.is_empty() => {
+                run_compound(compound, &mut ctx, results);
+                return results.last().map_or(0, CommandResult::status);
+            }
+            (compound, Ok(redirections)) => {
+                let mut stage = Vec::new();
+                run_compound(compound, &mut ctx, &mut stage);
+                redirections.apply(merge(stage), fs)
+            }
+        };
+
+        if i == last {
+            let status = result.status();
+            results.push(result);
+            return status;
+        }
+
+        let mut piped = Vec::new();
+        for result in result.flatten() {
+            if let CommandResult::Output { stdout, stderr, .. } = result {
+                // Errors are not piped, they go straight to the terminal
+                if !stderr.is_empty() {
+                    results.push(CommandResult::Output {
+                        stdout: Vec::new(),
+                        stderr,
+                        status: 0,
+                    });
+                }
+                piped.extend(stdout);
+            }
+        }
+        stdin = Some(piped);
+    }
+
+    0
+}
+
+/// Run a function definition or compound command as one pipeline stage,
+/// adding the results of the commands it runs. It fails if the last of
+/// them did.
+fn run_compound(command: &Command, ctx: &mut Context, results: &mut Vec<CommandResult>) {
+    match command {
+        Command::Simple(_) | Command::Redirected { .. } => {
+            unreachable!("commands with redirects run in run_pipeline")
+        }
+        Command::Function { name, body } => {
+            ctx.env.set_function(name, body.clone());
+            results.push(CommandResult::output(vec![]));
+        }
+        Command::Group(list) => {
+            run_body(list, ctx, results);
+        }
+        Command::If { branches, otherwise } => {
+            let mut chosen = otherwise.as_ref();
+            for (condition, body) in branches {
+                let passed = run_body(condition, ctx, results);
+                if exits(results.last()) {
+                    return;
+                }
+                if passed {
+                    chosen = Some(body);
+                    break;
+                }
+            }
+            match chosen {
+                Some(body) => {
+                    run_body(body, ctx, results);
+                }
+                // No branch ran, which is not a failure
+                None => results.push(CommandResult::output(vec![])),
+            }
+        }
+        Command::For { var, words, body } => {
+            let values = match words {
+                Some(words) => expand_words(words, ctx.env, ctx.fs, ctx.cwd),
+                None => ctx.env.positional().to_vec(),
+            };
+            let start = results.len();
+            for value in values {
+                ctx.env.set(var, &value);
+                run_body(body, ctx, results);
+                if exits(results.last()) {
+                    return;
+                }
+            }
+            if results.len() == start {
+                results.push(CommandResult::output(vec![]));
+            }
+        }
+        Command::While { condition, body, until } => {
+            for iteration in 0.. {
+                let passed = run_body(condition, ctx, results);
+                if exits(results.last()) {
+                    return;
+                }
+                if passed == *until {
+                    break;
+                }
+                if iteration == MAX_ITERATIONS {
+                    results.push(error(&format!(
+                        "rsh: loop stopped after {} passes",
+                        MAX_ITERATIONS
+                    )));
+                    return;
+                }
+                run_body(body, ctx, results);
+                if exits(results.last()) {
+                    return;
+                }
+            }
+            // The condition failing is how the loop ends, not a failure
+            results.push(CommandResult::output(vec![]));
+        }
+    }
+}
+
+/// Run a list inside a compound command, collecting its results.
+/// Returns whether its last pipeline succeeded.
+fn run_body(list: &CommandList, ctx: &mut Context, results: &mut Vec<CommandResult>) -> bool {
+    results.extend(run_list(list, ctx.fs, ctx.cwd, ctx.env, ctx.history));
+    ctx.env.status() == 0
+}
+
+/// Where a command's stdout and stderr go, with any files they are
+/// redirected to already opened
+struct Redirections {
+    stdout: Sink,
+    stderr: Sink,
+    /// Each file as typed, and its resolved path
+    files: Vec<(String, String)>,
+}
+
+impl Redirections {
+    /// Output goes where it would without redirections
+    fn none() -> Self {
+        Redirections {
+            stdout: Sink::Stdout,
+            stderr: Sink::Stderr,
+            files: Vec::new(),
+        }
+    }
+
+    /// Send the command's stdout and stderr to their files, or on to the
+    /// pipe and terminal
+    fn apply(&self, result: CommandResult, fs: &mut VirtualFS) -> CommandResult {
+        let (stdout, stderr, status) = match result {
+            CommandResult::Output { stdout, stderr, status } => (stdout, stderr, status),
+            CommandResult::Sequence(results) => {
+                let results = results.into_iter().map(|result| self.apply(result, fs));
+                return CommandResult::Sequence(results.collect());
+            }
+            other => return other,
+        };
+        let mut contents = vec![String::new(); self.files.len()];
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        for (lines, sink) in [(stdout, self.stdout), (stderr, self.stderr)] {
+            match sink {
+                Sink::Stdout => out.extend(lines),
+                Sink::Stderr => err.extend(lines),
+                Sink::File(i) => {
+                    for line in &lines {
+                        contents[i].push_str(&ui::line_to_string(line));
+                        contents[i].push('\n');
+                    }
+                }
+                Sink::Discard => {}
+            }
+        }
+        // The files were opened before the command ran, so its output is
+        // added to the end
+        for ((target, path), content) in self.files.iter().zip(contents) {
+            if content.is_empty() {
+                continue;
+            }
+            if let Err(e) = fs.write_file(path, &content, true) {
+                err.push(ui::error_text(&format!("rsh: {}: {}", target, e)));
+            }
+        }
+        CommandResult::Output {
+            stdout: out,
+            stderr: err,
+            status,
+        }
+    }
+}
+
+/// Open a command's redirection files, creating them and emptying any not
+/// appended to, as a real shell does before the command starts
+fn open_redirects(
+    redirects: &[Redirect],
+    fs: &mut VirtualFS,
+    cwd: &str,
+    env: &Environment,
+) -> Result<Redirections, CommandResult> {
+    let mut redirections = Redirections::none();
+    for redirect in redirects {
+        let sink = match &redirect.target {
+            RedirectTarget::File { path, append } => {
+                let target = redirect_target(path, env)?;
+                let path = fs.resolve_path(&target, cwd);
+                if path == DEV_NULL {
+                    Sink::Discard
+                } else if let Err(e) = fs.write_file(&path, "", *append) {
+                    return Err(redirect_error(&target, &e));
+                } else {
+                    redirections.files.push((target, path));
+                    Sink::File(redirections.files.len() - 1)
+                }
+            }
+            RedirectTarget::Fd(1) => redirections.stdout,
+            RedirectTarget::Fd(2) => redirections.stderr,
+            RedirectTarget::Fd(fd) => return Err(redirect_error(&fd.to_string(), "Bad file descriptor")),
+        };
+        match redirect.fd {
+            1 => redirections.stdout = sink,
+            2 => redirections.stderr = sink,
+            _ => {}
+        }
+    }
+    Ok(redirections)
+}
+
+/// Where a redirected stream ends up
+#[derive(Clone, Copy)]
+enum Sink {
+    /// On to the next stage or the terminal, as if not redirected
+    Stdout,
+    /// To the terminal as errors
+    Stderr,
+    /// Index into the files being written
+    File(usize),
+    /// `/dev/null`
+    Discard,
+}
+
+/// A redirection target must expand to exactly one word
+fn redirect_target(word: &Word, env: &Environment) -> Result<String, CommandResult> {
+    let mut fields = expand_word(word, env);
+    if fields.len() == 1 {
+        Ok(fields.remove(0))
+    } else {
+        Err(redirect_error(&word.as_source(), "ambiguous redirect"))
+    }
+}
+
+fn redirect_error(target: &str, reason: &str) -> CommandResult {
+    error(&format!("rsh: {}: {}", target, reason))
+}
+
+/// Run a single command. Leading `NAME=value` words set shell variables
+/// when they are all there is, and are exported to the command for its
+/// run only when one follows.
+fn run_simple_command(command: &SimpleCommand, ctx: &mut Context) -> CommandResult {
+    let mut words = command.words.as_slice();
+    let mut assignments = Vec::new();
+    while let Some((word, rest)) = words.split_first()
+        && let Some(assignment) = expand_assignment(word, ctx.env)
+    {
+        assignments.push(assignment);
+        words = rest;
+    }
+
+    if words.is_empty() {
+        for (name, value) in assignments {
+            ctx.env.set(&name, &value);
+        }
+        return CommandResult::output(vec![]);
+    }
+
+    let words = expand_words(words, ctx.env, ctx.fs, ctx.cwd);
+    let parts: Vec<&str> = words.iter().map(String::as_str).collect();
+    if assignments.is_empty() {
+        return run_argv(&parts, ctx);
+    }
+    let saved = ctx
+        .env
+        .set_temporary(assignments.iter().map(|(name, value)| (name.as_str(), value.as_str())));
+    let result = run_argv(&parts, ctx);
+    ctx.env.restore_temporary(saved);
+    result
+}
+
+/// Run an expanded command line: a function, a builtin, or an executable
+/// named by path or found in the home directory
+pub fn run_argv(parts: &[&str], ctx: &mut Context) -> CommandResult {
+    let Some((&name, args)) = parts.split_first() else {
+        return CommandResult::output(vec![]);
+    };
+
+    if let Some(body) = ctx.env.function(name).cloned() {
+        if let Err(e) = ctx.env.enter() {
+            return error(&format!("rsh: {}: {}", name, e));
+        }
+        let saved = ctx.env.set_positional(None, args.iter().map(ToString::to_string).collect());
+        let results = run_list(&body, ctx.fs, ctx.cwd, ctx.env, ctx.history);
+        ctx.env.restore_positional(saved);
+        ctx.env.leave();
+        return merge(results).with_status(ctx.env.status());
+    }
+
+    if let Some(builtin) = find(name) {
+        if args.first() == Some(&"--help") {
+            return CommandResult::output(short_usage(builtin));
+        }
+        return builtin.run(args, ctx);
+    }
+    if name.contains('/') {
+        let path = ctx.fs.resolve_path(name, ctx.cwd);
+        return run_executable(name, &path, args, ctx);
+    }
+    let path = child_path(HOME, name);
+    if let Some(FSEntry::Executable { .. }) = ctx.fs.get(&path) {
+        return run_executable(name, &path, args, ctx);
+    }
+    error(&format!("rsh: command not found: {}", name)).with_status(NOT_FOUND_STATUS)
+}
+
+/// Launch the sub-app behind an executable file, or run a script
+fn run_executable(name: &str, path: &str, args: &[&str], ctx: &mut Context) -> CommandResult {
+    let script = match ctx.fs.stat(path) {
+        Ok(FSEntry::File { content, .. }) if is_script(content) => Some(content.clone()),
+        _ => None,
+    };
+    if let Some(script) = script {
+        return run_script(name, &script, args, ctx);
+    }
+
+    let (reason, status) = match ctx.fs.stat(path) {
+        Ok(FSEntry::Executable { .. }) => {
+            let file_name = path.rsplit('/').next().unwrap_or(path);
+            match APPS.iter().find(|app| **app == file_name) {
+                Some(app) => return CommandResult::AppLaunch(app),
+                None => ("cannot execute binary file".to_string(), NOT_EXECUTABLE_STATUS),
+            }
+        }
+        Ok(FSEntry::Directory { .. }) => ("Is a directory".to_string(), NOT_EXECUTABLE_STATUS),
+        Ok(_) => ("Permission denied".to_string(), NOT_EXECUTABLE_STATUS),
+        Err(reason) => (reason, NOT_FOUND_STATUS),
+    };
+    error(&format!("rsh: {}: {}", name, reason)).with_status(status)
+}
+
+/// Whether `./name` can run an entry: an app, or a script. Scripts run
+/// whatever their mode bits, since visitors have no `chmod`.
+pub fn is_runnable(entry: &FSEntry) -> bool {
+    match entry {
+        FSEntry::Executable { .. } => true,
+        FSEntry::File { content, .. } => is_script(content),
+        _ => false,
+    }
+}
+
+/// Whether a file starts with a `#!` line naming this shell
+fn is_script(content: &str) -> bool {
+    let Some(interpreter) = content.lines().next().and_then(|line| line.strip_prefix("#!")) else {
+        return false;
+    };
+    // `#!/bin/rsh`, or `#!/usr/bin/env sh` and the like
+    let program = interpreter.split_whitespace().last().unwrap_or_default();
+    matches!(program.rsplit('/').next(), Some("rsh" | "sh"))
+}
+
+/// Run a script as a child shell would: `$0` is its name and its arguments
+/// are `$1` onwards, and its variables, functions and working directory
+/// are put back when it finishes. `exit` ends the script, not the session.
+pub fn run_script(name: &str, script: &str, args: &[&str], ctx: &mut Context) -> CommandResult {
+    if let Err(e) = ctx.env.enter() {
+        return error(&format!("rsh: {}: {}", name, e));
+    }
+    let env = ctx.env.clone();
+    let cwd = ctx.cwd.clone();
+    ctx.env.set_positional(Some(name), args.iter().map(ToString::to_string).collect());
+
+    let mut results: Vec<_> = execute_command(script, ctx.fs, ctx.cwd, ctx.env, ctx.history)
+        .into_iter()
+        .flat_map(CommandResult::flatten)
+        .collect();
+    let mut status = ctx.env.status();
+    *ctx.env = env;
+    *ctx.cwd = cwd;
+    ctx.env.leave();
+
+    if let Some(CommandResult::Exit(code)) = results.last() {
+        status = *code;
+        results.pop();
+    }
+    merge(results).with_status(status)
+}
+
+/// Fold the results of running a list into one, as if a single command had
+/// printed all the lines, with the status of the last command. Anything but
+/// lines, like a page or an app launch, keeps its place between them in a
+/// [`CommandResult::Sequence`].
+pub fn merge(results: Vec<CommandResult>) -> CommandResult {
+    let mut merged: Vec<CommandResult> = Vec::new();
+    for result in results.into_iter().flat_map(CommandResult::flatten) {
+        match (merged.last_mut(), result) {
+            (
+                Some(CommandResult::Output { stdout, stderr, status }),
+                CommandResult::Output {
+                    stdout: out,
+                    stderr: err,
+                    status: last,
+                },
+            ) => {
+                stdout.extend(out);
+                stderr.extend(err);
+                *status = last;
+            }
+            (_, result) => merged.push(result),
+        }
+    }
+    match merged.len() {
+        0 => CommandResult::output(vec![]),
+        1 => merged.remove(0),
+        _ => CommandResult::Sequence(merged),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::content::{ContentNode, NodeData};
+
+    /// A session in ~, which holds notes.txt
+    struct Session {
+        fs: VirtualFS,
+        cwd: String,
+        env: Environment,
+        history: History,
+    }
+
+    impl Session {
+        fn new() -> Self {
+            let node = |path: &str, data| ContentNode {
+                path: path.to_string(),
+                data,
+                mode: 0o644,
+                mtime: 0,
+            };
+            let fs = VirtualFS::from_content(vec![
+                node("", NodeData::Dir),
+                node("notes.txt", NodeData::File(b"one\ntwo\n".as_slice().into())),
+            ])
+            .unwrap();
+            Session {
+                fs,
+                cwd: HOME.to_string(),
+                env: Environment::new(),
+                history: History::new(),
+            }
+        }
+
+        /// Run a command line; returns what reached the terminal as
+        /// stdout and stderr text
+        fn run(&mut self, line: &str) -> (Vec<String>, Vec<String>) {
+            let results =
+                execute_command(line, &mut self.fs, &mut self.cwd, &mut self.env, &mut self.history);
+            let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+            for result in results.into_iter().flat_map(CommandResult::flatten) {
+                if let CommandResult::Output { stdout: out, stderr: err, .. } = result {
+                    stdout.extend(out.iter().map(ui::line_to_string));
+                    stderr.extend(err.iter().map(ui::line_to_string));
+                }
+            }
+            (stdout, stderr)
+        }
+
+        fn status(&self) -> i32 {
+            self.env.status()
+        }
+
+        /// Contents of a file in ~
+        fn read(&self, name: &str) -> Option<String> {
+            match self.fs.get(&child_path(HOME, name)) {
+                Some(FSEntry::File { content, .. }) => Some(content.clone()),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn errors_go_to_stderr_and_skip_pipes() {
+        let mut session = Session::new();
+        let (stdout, stderr) = session.run("cat missing.txt notes.txt | wc -l");
+        assert_eq!(stdout, ["2"]);
+        assert_eq!(stderr, ["cat: missing.txt: No such file or directory"]);
+        assert_eq!(session.status(), 0);
+    }
+
+    #[test]
+    fn output_redirections() {
+        let mut session = Session::new();
+        assert_eq!(session.run("echo a > out; echo b >> out"), (vec![], vec![]));
+        assert_eq!(session.read("out").unwrap(), "a\nb\n");
+        session.run("echo c > out");
+        assert_eq!(session.read("out").unwrap(), "c\n");
+    }
+
+    #[test]
+    fn stderr_redirections() {
+        let mut session = Session::new();
+        assert_eq!(session.run("cat missing.txt 2> err"), (vec![], vec![]));
+        assert_eq!(session.read("err").unwrap(), "cat: missing.txt: No such file or directory\n");
+        assert_eq!(session.status(), 1);
+
+        assert_eq!(session.run("cat missing.txt 2>/dev/null"), (vec![], vec![]));
+        assert_eq!(session.status(), 1);
+    }
+
+    #[test]
+    fn duplicating_follows_the_order_written() {
+        let mut session = Session::new();
+        // stderr joins stdout after stdout has gone to the file
+        assert_eq!(session.run("cat notes.txt missing.txt > both 2>&1"), (vec![], vec![]));
+        assert_eq!(
+            session.read("both").unwrap(),
+            "one\ntwo\ncat: missing.txt: No such file or directory\n"
+        );
+        // stderr goes where stdout went before it was redirected
+        let (stdout, stderr) = session.run("cat missing.txt 2>&1 > out");
+        assert_eq!(stdout, ["cat: missing.txt: No such file or directory"]);
+        assert!(stderr.is_empty());
+        assert_eq!(session.read("out").unwrap(), "");
+        // and so into the pipe
+        assert_eq!(session.run("cat missing.txt 2>&1 | wc -l").0, ["1"]);
+    }
+
+    #[test]
+    fn files_are_opened_before_the_command_runs() {
+        let mut session = Session::new();
+        // Truncated first, as in a real shell
+        session.run("cat notes.txt > notes.txt");
+        assert_eq!(session.read("notes.txt").unwrap(), "");
+        // The file already exists when ls lists the directory
+        session.run("ls > listing");
+        assert!(session.read("listing").unwrap().lines().any(|line| line == "listing"));
+        // A redirection that fails stops the command
+        let (_, stderr) = session.run("rm listing > /nope/out");
+        assert_eq!(stderr, ["rsh: /nope/out: No such file or directory"]);
+        assert_eq!(session.status(), 1);
+        assert!(session.read("listing").is_some());
+    }
+
+    #[test]
+    fn status_reaches_the_next_command() {
+        let mut session = Session::new();
+        assert_eq!(session.run("false; echo $?").0, ["1"]);
+        assert_eq!(session.run("true; echo $?").0, ["0"]);
+        assert_eq!(session.run("nosuchcommand; echo $?").0, ["127"]);
+        assert_eq!(session.run("false | true; echo $?").0, ["0"]);
+        assert_eq!(session.run("false && echo no || echo yes").0, ["yes"]);
+        session.run("cat missing.txt");
+        assert_eq!(session.status(), 1);
+        assert_eq!(session.run("echo $?").0, ["1"]);
+    }
+
+    #[test]
+    fn compound_commands_redirect_as_a_whole() {
+        let mut session = Session::new();
+        session.run("for i in 1 2; do echo $i; done > out");
+        assert_eq!(session.read("out").unwrap(), "1\n2\n");
+        session.run("{ echo a; cat missing.txt; } > out 2>&1");
+        assert_eq!(session.read("out").unwrap(), "a\ncat: missing.txt: No such file or directory\n");
+    }
+}
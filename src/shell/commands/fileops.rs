@@ -0,0 +1,493 @@
+//! File operations - every change lands in the session overlay
+
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+
+use super::{error, file_op_result, split_flags, ArgKind, Command, CommandResult, Context};
+use crate::shell::filesystem::child_path;
+use crate::shell::{FSEntry, VirtualFS};
+use crate::theme::Theme;
+use crate::ui;
+
+pub struct Touch;
+
+impl Command for Touch {
+    fn name(&self) -> &'static str {
+        "touch"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "touch <file...>"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Create an empty file"
+    }
+
+    fn help(&self) -> &'static str {
+        "Create each file that does not exist yet, and update the \
+         modification time of those that do."
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        touch(args, ctx.fs, ctx.cwd)
+    }
+}
+
+pub struct Mkdir;
+
+impl Command for Mkdir {
+    fn name(&self) -> &'static str {
+        "mkdir"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "mkdir [-p] <dir...>"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Create directories"
+    }
+
+    fn help(&self) -> &'static str {
+        "Create each directory. With -p, create missing parents too and \
+         accept directories that already exist."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-p", "create missing parents; existing directories are fine")]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[("mkdir -p notes/2026", "make notes and notes/2026")]
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Dirs
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        make_dirs(args, ctx.fs, ctx.cwd)
+    }
+}
+
+pub struct Cp;
+
+impl Command for Cp {
+    fn name(&self) -> &'static str {
+        "cp"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "cp [-r] <src...> <dest>"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Copy files"
+    }
+
+    fn help(&self) -> &'static str {
+        "Copy files, or whole directories with -r (also -R or -a). With \
+         several sources, dest must be a directory. Copies belong to you."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-r", "copy directories and everything in them (also -R, -a)")]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("cp README.md notes.md", "copy a file"),
+            ("cp -r blog backup", "copy the whole blog"),
+        ]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        copy(args, ctx.fs, ctx.cwd)
+    }
+}
+
+pub struct Mv;
+
+impl Command for Mv {
+    fn name(&self) -> &'static str {
+        "mv"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "mv <src...> <dest>"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Move or rename files"
+    }
+
+    fn help(&self) -> &'static str {
+        "Move each source to dest, or into it when dest is a directory. \
+         Metadata is kept."
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        move_entries(args, ctx.fs, ctx.cwd)
+    }
+}
+
+pub struct Rm;
+
+impl Command for Rm {
+    fn name(&self) -> &'static str {
+        "rm"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "rm [-rf] <file...>"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Remove files"
+    }
+
+    fn help(&self) -> &'static str {
+        "Remove files, or directories and everything in them with -r. \
+         With -f, missing files are not an error. Changes only last for \
+         this session."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-r", "remove directories and their contents (also -R)"),
+            ("-f", "ignore files that don't exist"),
+        ]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[("rm -rf backup", "remove a directory and everything in it")]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        remove(args, ctx.fs, ctx.cwd)
+    }
+}
+
+pub struct Ln;
+
+impl Command for Ln {
+    fn name(&self) -> &'static str {
+        "ln"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "ln -s <target> [link]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Create a symbolic link"
+    }
+
+    fn help(&self) -> &'static str {
+        "Create a symbolic link to target, named link or the target's \
+         name. The target is stored as typed, so relative targets are \
+         relative to the link. There are no hard links: each path here \
+         is its own entry, so a symbolic link is the way to show the same \
+         file in two places."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-s", "make a symbolic link; required, as hard links are not supported"),
+            ("-f", "replace an existing link"),
+        ]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[("ln -s blog/2026 posts", "posts now leads to this year's blog")]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        link(args, ctx.fs, ctx.cwd)
+    }
+}
+
+pub struct Nano;
+
+impl Command for Nano {
+    fn name(&self) -> &'static str {
+        "nano"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["edit"]
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "nano <file>"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Edit a file"
+    }
+
+    fn help(&self) -> &'static str {
+        "Open file in a small editor: ^S saves, ^X exits, ^K cuts a line. \
+         The file is created on first save."
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        edit(args, ctx.fs, ctx.cwd)
+    }
+}
+
+fn touch(args: &[&str], fs: &mut VirtualFS, cwd: &str) -> CommandResult {
+    let (_, operands) = split_flags(args);
+    if operands.is_empty() {
+        return error("touch: missing file operand");
+    }
+    let mut errors = Vec::new();
+    for operand in operands {
+        if let Err(e) = fs.touch(&fs.resolve_path(operand, cwd)) {
+            errors.push(ui::error_text(&format!("touch: cannot touch '{}': {}", operand, e)));
+        }
+    }
+    file_op_result(errors)
+}
+
+fn make_dirs(args: &[&str], fs: &mut VirtualFS, cwd: &str) -> CommandResult {
+    let (flags, operands) = split_flags(args);
+    let parents = flags.contains(&'p');
+    if operands.is_empty() {
+        return error("mkdir: missing operand");
+    }
+    let mut errors = Vec::new();
+    for operand in operands {
+        let path = fs.resolve_path(operand, cwd);
+        let result = if parents {
+            // Create each missing ancestor in turn; existing directories are fine
+            let mut current = "/".to_string();
+            let mut result = Ok(());
+            for component in path.split('/').filter(|c| !c.is_empty()) {
+                current = child_path(&current, component);
+                match fs.get(&current) {
+                    Some(FSEntry::Directory { .. }) => continue,
+                    Some(_) => result = Err("Not a directory".to_string()),
+                    None => result = fs.create_dir(&current),
+                }
+                if result.is_err() {
+                    break;
+                }
+            }
+            result
+        } else {
+            fs.create_dir(&path)
+        };
+        if let Err(e) = result {
+            errors.push(ui::error_text(&format!(
+                "mkdir: cannot create directory '{}': {}",
+                operand, e
+            )));
+        }
+    }
+    file_op_result(errors)
+}
+
+fn remove(args: &[&str], fs: &mut VirtualFS, cwd: &str) -> CommandResult {
+    let (flags, operands) = split_flags(args);
+    let recursive = flags.contains(&'r') || flags.contains(&'R');
+    let force = flags.contains(&'f');
+
+    if recursive && operands.iter().any(|o| *o == "/" || *o == "/*") {
+        return CommandResult::failed(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "⚠️  rm: refusing to remove '/' recursively",
+                Style::default().fg(Theme::WARNING),
+            )),
+            Line::from(Span::styled(
+                "   This is a sandboxed portfolio, nice try! 😉",
+                Style::default().fg(Theme::MUTED),
+            )),
+            Line::from(""),
+        ]);
+    }
+    if operands.is_empty() && !force {
+        return error("rm: missing operand");
+    }
+
+    // The shell has to stay somewhere that exists
+    let real_cwd = fs.follow(cwd).unwrap_or_else(|_| cwd.to_string());
+    let holds_cwd = |path: &str| {
+        [cwd, real_cwd.as_str()]
+            .iter()
+            .any(|dir| *dir == path || dir.starts_with(&format!("{}/", path)))
+    };
+
+    let mut errors = Vec::new();
+    for operand in operands {
+        if matches!(operand.trim_end_matches('/').rsplit('/').next(), Some("." | "..")) {
+            errors.push(ui::error_text(&format!(
+                "rm: refusing to remove '.' or '..' directory: skipping '{}'",
+                operand
+            )));
+            continue;
+        }
+        let path = fs.resolve_path(operand, cwd);
+        if force && fs.get(&path).is_none() {
+            continue;
+        }
+        if recursive && holds_cwd(&path) {
+            errors.push(ui::error_text(&format!(
+                "rm: cannot remove '{}': it holds the current directory",
+                operand
+            )));
+            continue;
+        }
+        if let Err(e) = fs.remove(&path, recursive) {
+            errors.push(ui::error_text(&format!("rm: cannot remove '{}': {}", operand, e)));
+        }
+    }
+    file_op_result(errors)
+}
+
+/// Where `source` lands when copied or moved to `dest`: inside it if it is a directory
+fn transfer_target(fs: &VirtualFS, source: &str, dest: &str) -> String {
+    match fs.follow(dest) {
+        Ok(dir) if fs.get(&dir).is_some_and(FSEntry::is_dir) => {
+            let name = source.rsplit('/').next().unwrap_or(source);
+            child_path(&dir, name)
+        }
+        _ => dest.to_string(),
+    }
+}
+
+/// Shared argument handling for `cp` and `mv`. `op` gets the source as
+/// typed, the resolved source and target, and the flags; it returns the
+/// full error message on failure.
+fn transfer(
+    name: &str,
+    args: &[&str],
+    fs: &mut VirtualFS,
+    cwd: &str,
+    mut op: impl FnMut(&mut VirtualFS, &str, &str, &str, &[char]) -> Result<(), String>,
+) -> CommandResult {
+    let (flags, operands) = split_flags(args);
+    let Some((dest, sources)) = operands.split_last() else {
+        return error(&format!("{}: missing file operand", name));
+    };
+    if sources.is_empty() {
+        return error(&format!(
+            "{}: missing destination file operand after '{}'",
+            name, dest
+        ));
+    }
+
+    let dest_path = fs.resolve_path(dest, cwd);
+    if sources.len() > 1 && !fs.stat(&dest_path).is_ok_and(FSEntry::is_dir) {
+        return error(&format!(
+            "{}: target '{}' is not a directory",
+            name, dest
+        ));
+    }
+
+    let mut errors = Vec::new();
+    for source in sources {
+        let source_path = fs.resolve_path(source, cwd);
+        let target = transfer_target(fs, &source_path, &dest_path);
+        if let Err(e) = op(fs, source, &source_path, &target, &flags) {
+            errors.push(ui::error_text(&e));
+        }
+    }
+    file_op_result(errors)
+}
+
+fn copy(args: &[&str], fs: &mut VirtualFS, cwd: &str) -> CommandResult {
+    transfer("cp", args, fs, cwd, |fs, source, from, to, flags| {
+        let recursive = flags.contains(&'r') || flags.contains(&'R') || flags.contains(&'a');
+        // Without -r a link is copied as the file it points to
+        let from = if recursive {
+            from.to_string()
+        } else {
+            fs.follow(from).map_err(|e| format!("cp: cannot stat '{}': {}", source, e))?
+        };
+        let from = from.as_str();
+        match fs.get(from) {
+            None => return Err(format!("cp: cannot stat '{}': No such file or directory", source)),
+            Some(FSEntry::Directory { .. }) if !recursive => {
+                return Err(format!("cp: -r not specified; omitting directory '{}'", source));
+            }
+            Some(_) => {}
+        }
+        fs.copy(from, to, recursive)
+            .map_err(|e| format!("cp: cannot copy '{}': {}", source, e))
+    })
+}
+
+fn move_entries(args: &[&str], fs: &mut VirtualFS, cwd: &str) -> CommandResult {
+    transfer("mv", args, fs, cwd, |fs, source, from, to, _| {
+        if fs.get(from).is_none() {
+            return Err(format!("mv: cannot stat '{}': No such file or directory", source));
+        }
+        fs.rename(from, to)
+            .map_err(|e| format!("mv: cannot move '{}': {}", source, e))
+    })
+}
+
+fn link(args: &[&str], fs: &mut VirtualFS, cwd: &str) -> CommandResult {
+    let (flags, operands) = split_flags(args);
+    if !flags.contains(&'s') {
+        return error(
+            "ln: hard links are not supported, use ln -s",
+        );
+    }
+    let (target, link) = match operands.as_slice() {
+        [target] => (*target, target.trim_end_matches('/').rsplit('/').next().unwrap_or(target)),
+        [target, link] => (*target, *link),
+        [] => return error("ln: missing file operand"),
+        _ => return error("ln: too many arguments"),
+    };
+
+    let link_path = transfer_target(fs, target, &fs.resolve_path(link, cwd));
+    if flags.contains(&'f') && fs.get(&link_path).is_some_and(|e| !e.is_dir()) {
+        let _ = fs.remove(&link_path, false);
+    }
+    // The target is stored as typed, so relative links stay relative to the link
+    match fs.symlink(target, &link_path) {
+        Ok(()) => CommandResult::output(vec![]),
+        Err(e) => error(&format!(
+            "ln: failed to create symbolic link '{}': {}",
+            link, e
+        )),
+    }
+}
+
+/// Open a file in the editor sub-app
+fn edit(args: &[&str], fs: &VirtualFS, cwd: &str) -> CommandResult {
+    let Some(name) = args.iter().find(|a| !a.starts_with('-')) else {
+        return error("nano: missing file operand");
+    };
+    let path = match fs.follow(&fs.resolve_path(name, cwd)) {
+        Ok(path) => path,
+        Err(reason) => {
+            return error(&format!("nano: {}: {}", name, reason));
+        }
+    };
+    let content = match fs.get(&path) {
+        Some(FSEntry::File { content, .. }) => content.clone(),
+        Some(FSEntry::Symlink { .. }) => unreachable!("follow resolves symlinks"),
+        None => String::new(),
+        Some(FSEntry::Directory { .. }) => {
+            return error(&format!("nano: {}: Is a directory", name));
+        }
+        Some(FSEntry::Binary { .. }) => {
+            return error(&format!("nano: {}: binary file", name));
+        }
+        Some(FSEntry::Executable { .. }) => {
+            return error(&format!("nano: {}: Permission denied", name));
+        }
+    };
+    CommandResult::Edit {
+        name: name.to_string(),
+        path,
+        content,
+    }
+}
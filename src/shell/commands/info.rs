@@ -0,0 +1,569 @@
+//! Session and system commands, plus `help` and `man` over the registry
+
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+
+use super::cat::columns;
+use super::{all, error, find, ArgKind, Command, CommandResult, Context};
+use crate::markdown;
+use crate::shell::filesystem::HOME;
+use crate::shell::FSEntry;
+use crate::theme::Theme;
+use crate::ui;
+
+pub struct Echo;
+
+impl Command for Echo {
+    fn name(&self) -> &'static str {
+        "echo"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "echo [text...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Print text"
+    }
+
+    fn help(&self) -> &'static str {
+        "Print the arguments separated by single spaces. Variables are \
+         expanded before echo sees them, so `echo $HOME` prints the home \
+         directory."
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("echo $USER", "print your user name"),
+            ("echo note >> notes.txt", "add a line to a file"),
+            ("echo 'no such section' >&2", "print an error message from a script"),
+        ]
+    }
+
+    fn run(&self, args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::output(vec![Line::from(args.join(" "))])
+    }
+}
+
+pub struct Date;
+
+impl Command for Date {
+    fn name(&self) -> &'static str {
+        "date"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "date"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Print the date"
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, _args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::output(vec![Line::from("Wed Jan 29 10:30:00 CAT 2026")])
+    }
+}
+
+pub struct Whoami;
+
+impl Command for Whoami {
+    fn name(&self) -> &'static str {
+        "whoami"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "whoami"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Print the current user"
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, _args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::output(vec![Line::from("guest")])
+    }
+}
+
+pub struct Uname;
+
+impl Command for Uname {
+    fn name(&self) -> &'static str {
+        "uname"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "uname [-a]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Print system information"
+    }
+
+    fn help(&self) -> &'static str {
+        "Print the system name."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-a", "print everything known about the system")]
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, args: &[&str], _ctx: &mut Context) -> CommandResult {
+        if args.contains(&"-a") {
+            CommandResult::output(vec![Line::from(
+                "PortfolioOS 2.0.26-uchindami #1 SMP Rust x86_64 GNU/Rust",
+            )])
+        } else {
+            CommandResult::output(vec![Line::from("PortfolioOS")])
+        }
+    }
+}
+
+pub struct History;
+
+impl Command for History {
+    fn name(&self) -> &'static str {
+        "history"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "history [-c] [n]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Show command history"
+    }
+
+    fn help(&self) -> &'static str {
+        "List this session's commands with their numbers, or only the last n.\n\n\
+         !!       the previous command\n\
+         !n       command number n\n\
+         !-n      the command n lines back\n\
+         !prefix  the newest command starting with prefix\n\
+         Ctrl-R   search backwards as you type"
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-c", "clear the list")]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("history 5", "the last five commands"),
+            ("!ls", "run the newest command starting with ls again"),
+        ]
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let count = match args {
+            [] => ctx.history.len(),
+            ["-c"] => {
+                ctx.history.clear();
+                return CommandResult::output(vec![]);
+            }
+            [n] => match n.parse::<usize>() {
+                Ok(n) => n,
+                Err(_) => return error(&format!("history: {}: numeric argument required", n)),
+            },
+            _ => return error("history: too many arguments"),
+        };
+
+        let skip = ctx.history.len().saturating_sub(count);
+        CommandResult::output(
+            ctx.history
+                .numbered()
+                .skip(skip)
+                .map(|(number, line)| Line::from(format!("{:>5}  {}", number, line)))
+                .collect(),
+        )
+    }
+}
+
+pub struct Clear;
+
+impl Command for Clear {
+    fn name(&self) -> &'static str {
+        "clear"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "clear"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Clear the screen"
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, _args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::Clear
+    }
+}
+
+pub struct Help;
+
+impl Command for Help {
+    fn name(&self) -> &'static str {
+        "help"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "help [command]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Show this help"
+    }
+
+    fn help(&self) -> &'static str {
+        "List the available commands and executables. With a command name, \
+         show that command's usage and description."
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Commands
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        match args.first() {
+            Some(name) => match find(name) {
+                Some(command) => CommandResult::output(usage(command)),
+                None => error(&format!("help: no help topics match '{}'", name)),
+            },
+            None => CommandResult::output(overview(ctx)),
+        }
+    }
+}
+
+/// Every visible command, then the executables in the home directory
+fn overview(ctx: &Context) -> Vec<Line<'static>> {
+    let commands: Vec<_> = all().iter().filter(|command| !command.hidden()).collect();
+    let executables: Vec<_> = ctx
+        .fs
+        .list_dir(HOME)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(name, entry)| match entry {
+            FSEntry::Executable { description, .. } => {
+                Some((format!("./{}", name), description.clone()))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let width = commands
+        .iter()
+        .map(|command| command.synopsis().len())
+        .chain(executables.iter().map(|(name, _)| name.len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = vec![Line::from(""), ui::heading("Available Commands:"), Line::from("")];
+    for command in commands {
+        lines.push(Line::from(format!(
+            "  {:<width$}  {}",
+            command.synopsis(),
+            command.summary()
+        )));
+    }
+    if !executables.is_empty() {
+        lines.extend([Line::from(""), ui::heading("Executables:"), Line::from("")]);
+        for (name, description) in executables {
+            lines.push(Line::from(format!("  {:<width$}  {}", name, description)));
+        }
+    }
+    lines.push(Line::from(""));
+    lines
+}
+
+/// `help <command>`: usage line, description and options
+fn usage(command: &dyn Command) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(format!("{}: {}", command.name(), command.synopsis()))];
+    let description = if command.help().is_empty() {
+        command.summary()
+    } else {
+        command.help()
+    };
+    lines.extend(indented(description));
+    if !command.options().is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(options(command, "    "));
+    }
+    lines
+}
+
+/// `<command> --help`: a short usage message pointing at the manual
+pub fn short_usage(command: &dyn Command) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(format!("Usage: {}", command.synopsis())),
+        Line::from(command.summary()),
+    ];
+    if !command.options().is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(options(command, "  "));
+    }
+    lines.extend([
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Full documentation: man {}", command.name()),
+            Style::default().fg(Theme::MUTED),
+        )),
+    ]);
+    lines
+}
+
+/// A command's options, one per line with their descriptions aligned
+fn options(command: &dyn Command, indent: &str) -> Vec<Line<'static>> {
+    let width = command
+        .options()
+        .iter()
+        .map(|(flag, _)| flag.len())
+        .max()
+        .unwrap_or(0);
+    command
+        .options()
+        .iter()
+        .map(|(flag, description)| {
+            Line::from(format!("{}{:<width$}  {}", indent, flag, description))
+        })
+        .collect()
+}
+
+/// Help text split at its line breaks, indented one level
+fn indented(text: &str) -> impl Iterator<Item = Line<'static>> + '_ {
+    text.lines().map(|line| {
+        if line.is_empty() {
+            Line::from("")
+        } else {
+            Line::from(format!("    {}", line))
+        }
+    })
+}
+
+/// Manual pages for the executables, as markdown under the home directory
+const MAN_DIR: &str = ".man";
+
+pub struct Man;
+
+impl Command for Man {
+    fn name(&self) -> &'static str {
+        "man"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "man [-k] <command|keyword>"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Show a command's manual page"
+    }
+
+    fn help(&self) -> &'static str {
+        "Show the manual page for a builtin or an executable in the pager. \
+         Builtin pages are generated from the command itself; executables \
+         have theirs written in ~/.man."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-k keyword", "list the pages whose name or summary mention keyword")]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("man grep", "how to search files"),
+            ("man -k file", "every command that deals with files"),
+        ]
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Commands
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        match args {
+            [] => error("What manual page do you want?"),
+            ["-k"] => error("apropos what?"),
+            ["-k", keyword, ..] => apropos(keyword, ctx),
+            [name, ..] => {
+                let (title, lines) = match find(name) {
+                    Some(command) => (command.name(), page(command)),
+                    None => {
+                        let name = name.trim_start_matches("./");
+                        match read_page(ctx, name) {
+                            Some(source) => (name, markdown::render(&source, columns(ctx.env))),
+                            None => return error(&format!("No manual entry for {}", name)),
+                        }
+                    }
+                };
+                if ctx.tty {
+                    CommandResult::Page {
+                        title: format!("{}(1)", title),
+                        lines,
+                    }
+                } else {
+                    CommandResult::output(lines)
+                }
+            }
+        }
+    }
+}
+
+/// A builtin's manual page, built from the registry
+fn page(command: &dyn Command) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        ui::heading("NAME"),
+        Line::from(format!("    {} - {}", command.name(), command.summary())),
+        Line::from(""),
+        ui::heading("SYNOPSIS"),
+        Line::from(format!("    {}", command.synopsis())),
+    ];
+    if !command.help().is_empty() {
+        lines.extend([Line::from(""), ui::heading("DESCRIPTION")]);
+        lines.extend(indented(command.help()));
+    }
+    if !command.options().is_empty() {
+        lines.extend([Line::from(""), ui::heading("OPTIONS")]);
+        lines.extend(options(command, "    "));
+    }
+    if !command.examples().is_empty() {
+        lines.extend([Line::from(""), ui::heading("EXAMPLES")]);
+        for (i, (example, description)) in command.examples().iter().enumerate() {
+            if i > 0 {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(vec![
+                Span::styled("    $ ", Style::default().fg(Theme::MUTED)),
+                Span::styled(example.to_string(), Style::default().fg(Theme::ACCENT)),
+            ]));
+            lines.push(Line::from(format!("        {}", description)));
+        }
+    }
+    if !command.aliases().is_empty() {
+        lines.extend([
+            Line::from(""),
+            ui::heading("ALIASES"),
+            Line::from(format!("    {}", command.aliases().join(", "))),
+        ]);
+    }
+    lines
+}
+
+/// Markdown source of an executable's page in `~/.man`
+fn read_page(ctx: &Context, name: &str) -> Option<String> {
+    let path = format!("{}/{}/{}.md", HOME, MAN_DIR, name);
+    match ctx.fs.stat(&path) {
+        Ok(FSEntry::File { content, .. }) => Some(content.clone()),
+        _ => None,
+    }
+}
+
+/// `man -k`: every page whose name or summary mentions the keyword
+fn apropos(keyword: &str, ctx: &Context) -> CommandResult {
+    let keyword = keyword.to_lowercase();
+    let mentions = |text: &str| text.to_lowercase().contains(&keyword);
+
+    let mut found: Vec<(String, String)> = all()
+        .iter()
+        .filter(|command| !command.hidden())
+        .filter(|command| {
+            mentions(command.name())
+                || command.aliases().iter().any(|alias| mentions(alias))
+                || mentions(command.summary())
+        })
+        .map(|command| (command.name().to_string(), command.summary().to_string()))
+        .collect();
+
+    let pages = ctx
+        .fs
+        .list_dir(&format!("{}/{}", HOME, MAN_DIR))
+        .unwrap_or_default();
+    for (file, entry) in pages {
+        let (Some(name), FSEntry::File { content, .. }) = (file.strip_suffix(".md"), entry) else {
+            continue;
+        };
+        // The summary is the NAME section's "name - description" line
+        let summary = content
+            .lines()
+            .find_map(|line| line.split_once(" - ").map(|(_, summary)| summary.trim()))
+            .unwrap_or_default();
+        if mentions(name) || mentions(summary) {
+            found.push((name.to_string(), summary.to_string()));
+        }
+    }
+    if found.is_empty() {
+        return error(&format!("{}: nothing appropriate.", keyword));
+    }
+
+    found.sort();
+    let width = found.iter().map(|(name, _)| name.len() + 4).max().unwrap_or(0);
+    CommandResult::output(
+        found
+            .into_iter()
+            .map(|(name, summary)| {
+                Line::from(format!("{:<width$} - {}", format!("{} (1)", name), summary))
+            })
+            .collect(),
+    )
+}
+
+pub struct Exit;
+
+impl Command for Exit {
+    fn name(&self) -> &'static str {
+        "exit"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["quit"]
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "exit [n]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Exit portfolio"
+    }
+
+    fn help(&self) -> &'static str {
+        "End the session. In a script run with sh, end the script instead, \
+         with status n, or the status of the last command when n is not \
+         given."
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        match args.first() {
+            None => CommandResult::Exit(ctx.env.status()),
+            // Statuses wrap to 0-255, as in bash
+            Some(n) => match n.parse::<i64>() {
+                Ok(status) => CommandResult::Exit(status.rem_euclid(256) as i32),
+                Err(_) => error(&format!("exit: {}: numeric argument required", n)),
+            },
+        }
+    }
+}
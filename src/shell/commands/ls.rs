@@ -0,0 +1,353 @@
+//! `ls` - list directory contents
+
+use std::collections::VecDeque;
+
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+
+use super::{error, split_flags, Command, CommandResult, Context};
+use crate::shell::env::Environment;
+use crate::shell::filesystem::{self, child_path, Metadata};
+use crate::shell::time;
+use crate::shell::{FSEntry, VirtualFS};
+use crate::theme::Theme;
+use crate::ui;
+
+pub struct Ls;
+
+impl Command for Ls {
+    fn name(&self) -> &'static str {
+        "ls"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "ls [-lahtrS1R] [file...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "List directory contents"
+    }
+
+    fn help(&self) -> &'static str {
+        "List each directory's contents, or the file itself for other operands. \
+         Names are laid out in columns on the terminal and one per line in pipes."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-l", "long listing: mode, links, owner, group, size, mtime"),
+            ("-a", "include hidden entries, . and .."),
+            ("-h", "human-readable sizes (with -l)"),
+            ("-t", "sort by modification time, newest first"),
+            ("-S", "sort by size, largest first"),
+            ("-r", "reverse the sort order"),
+            ("-1", "one name per line"),
+            ("-R", "list subdirectories recursively"),
+        ]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("ls -la", "everything here, hidden files included, in detail"),
+            ("ls -lhS downloads", "the downloads, largest first"),
+        ]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        list(args, ctx.fs, ctx.cwd, ctx.env, ctx.tty)
+    }
+}
+
+/// Flags accepted by `ls`
+#[derive(Default)]
+struct LsOptions {
+    long: bool,
+    all: bool,
+    human: bool,
+    by_time: bool,
+    by_size: bool,
+    reverse: bool,
+    one_per_line: bool,
+    recursive: bool,
+}
+
+/// Something `ls` prints: a name and the entry it describes
+type LsItem<'a> = (String, &'a FSEntry);
+
+fn list(args: &[&str], fs: &VirtualFS, cwd: &str, env: &Environment, tty: bool) -> CommandResult {
+    let (flags, mut operands) = split_flags(args);
+    let mut opts = LsOptions::default();
+    for flag in flags {
+        match flag {
+            'l' => opts.long = true,
+            'a' => opts.all = true,
+            'h' => opts.human = true,
+            't' => opts.by_time = true,
+            'S' => opts.by_size = true,
+            'r' => opts.reverse = true,
+            '1' => opts.one_per_line = true,
+            'R' => opts.recursive = true,
+            other => {
+                return error(&format!("ls: invalid option -- '{}'", other));
+            }
+        }
+    }
+    // Grid output only makes sense on the terminal; pipes get one name per line
+    let width = match env.get("COLUMNS").and_then(|c| c.parse().ok()) {
+        Some(width) if tty && !opts.one_per_line => width,
+        _ => 0,
+    };
+    if operands.is_empty() {
+        operands.push(".");
+    }
+
+    let mut errors = Vec::new();
+    let mut files = Vec::new();
+    let mut dirs = Vec::new();
+    for operand in &operands {
+        let path = fs.resolve_path(operand, cwd);
+        let Some(entry) = fs.get(&path) else {
+            errors.push(ui::error_text(&format!(
+                "ls: cannot access '{}': No such file or directory",
+                operand
+            )));
+            continue;
+        };
+        // A link to a directory is listed as the directory, except by `ls -l`
+        let follow =
+            matches!(entry, FSEntry::Symlink { .. }) && (!opts.long || operand.ends_with('/'));
+        let target = if follow { fs.follow(&path) } else { Ok(path.clone()) };
+        match target {
+            Ok(target) if fs.get(&target).is_some_and(FSEntry::is_dir) => {
+                dirs.push((operand.to_string(), target));
+            }
+            Ok(_) => files.push((operand.to_string(), entry)),
+            Err(reason) => errors.push(ui::error_text(&format!(
+                "ls: cannot access '{}': {}",
+                operand, reason
+            ))),
+        }
+    }
+
+    let mut lines = Vec::new();
+    let now = time::now();
+    if !files.is_empty() {
+        sort_ls_items(&mut files, &opts);
+        lines.extend(format_ls_items(&files, &opts, width, now));
+    }
+
+    let show_headers = operands.len() > 1 || opts.recursive;
+    let mut queue: VecDeque<(String, String)> = dirs.into_iter().collect();
+    while let Some((display, path)) = queue.pop_front() {
+        let mut items = ls_dir_items(fs, &path, opts.all);
+        sort_ls_items(&mut items, &opts);
+
+        if show_headers {
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(format!("{}:", display)));
+        }
+        if opts.long {
+            let blocks: usize = items.iter().map(|(_, e)| e.meta().size.div_ceil(1024)).sum();
+            let total = if opts.human { human_size(blocks * 1024) } else { blocks.to_string() };
+            lines.push(Line::from(format!("total {}", total)));
+        }
+        lines.extend(format_ls_items(&items, &opts, width, now));
+
+        if opts.recursive {
+            // Subdirectories are listed right after their parent, in display order
+            let subdirs = items
+                .iter()
+                .filter(|(name, entry)| entry.is_dir() && name != "." && name != "..")
+                .map(|(name, _)| {
+                    let display = format!("{}/{}", display.trim_end_matches('/'), name);
+                    (display, child_path(&path, name))
+                });
+            for (i, subdir) in subdirs.enumerate() {
+                queue.insert(i, subdir);
+            }
+        }
+    }
+
+    CommandResult::new(lines, errors)
+}
+
+/// Entries of a directory, with `.` and `..` when showing hidden files
+fn ls_dir_items<'a>(fs: &'a VirtualFS, path: &str, all: bool) -> Vec<LsItem<'a>> {
+    let mut items = Vec::new();
+    if all {
+        if let Some(entry) = fs.get(path) {
+            items.push((".".to_string(), entry));
+        }
+        if let Some(entry) = fs.get(filesystem::parent(path)) {
+            items.push(("..".to_string(), entry));
+        }
+    }
+    for (name, entry) in fs.list_dir(path).unwrap_or_default() {
+        if all || !name.starts_with('.') {
+            items.push((name, entry));
+        }
+    }
+    items
+}
+
+fn sort_ls_items(items: &mut [LsItem], opts: &LsOptions) {
+    items.sort_by(|(a_name, a), (b_name, b)| {
+        let (a, b) = (a.meta(), b.meta());
+        let order = if opts.by_size {
+            b.size.cmp(&a.size)
+        } else if opts.by_time {
+            b.mtime.cmp(&a.mtime)
+        } else {
+            std::cmp::Ordering::Equal
+        };
+        order.then_with(|| a_name.cmp(b_name))
+    });
+    if opts.reverse {
+        items.reverse();
+    }
+}
+
+/// A name styled by entry type; directories get a trailing slash
+pub fn ls_name(name: &str, entry: &FSEntry) -> Span<'static> {
+    match entry {
+        FSEntry::Directory { .. } if name == "." || name == ".." => {
+            Span::styled(name.to_string(), Style::default().fg(Theme::PRIMARY))
+        }
+        FSEntry::Directory { .. } => Span::styled(
+            format!("{}/", name.trim_end_matches('/')),
+            Style::default().fg(Theme::PRIMARY),
+        ),
+        FSEntry::Executable { .. } => {
+            Span::styled(name.to_string(), Style::default().fg(Theme::SUCCESS))
+        }
+        FSEntry::Symlink { .. } => {
+            Span::styled(name.to_string(), Style::default().fg(Theme::HIGHLIGHT))
+        }
+        FSEntry::File { .. } | FSEntry::Binary { .. } => Span::raw(name.to_string()),
+    }
+}
+
+fn format_ls_items(items: &[LsItem], opts: &LsOptions, width: usize, now: u64) -> Vec<Line<'static>> {
+    let names: Vec<Span<'static>> = items.iter().map(|(name, entry)| ls_name(name, entry)).collect();
+    if opts.long {
+        format_ls_long(items, names, opts.human, now)
+    } else if width > 0 {
+        format_ls_grid(names, width)
+    } else {
+        names.into_iter().map(Line::from).collect()
+    }
+}
+
+/// `ls -l`: one entry per line with every column aligned
+fn format_ls_long(
+    items: &[LsItem],
+    names: Vec<Span<'static>>,
+    human: bool,
+    now: u64,
+) -> Vec<Line<'static>> {
+    let sizes: Vec<String> = items
+        .iter()
+        .map(|(_, entry)| {
+            let size = entry.meta().size;
+            if human { human_size(size) } else { size.to_string() }
+        })
+        .collect();
+    let column = |f: &dyn Fn(&Metadata) -> usize| {
+        items.iter().map(|(_, entry)| f(entry.meta())).max().unwrap_or(0)
+    };
+    let link_width = column(&|m| m.nlink.to_string().len());
+    let owner_width = column(&|m| m.owner.len());
+    let group_width = column(&|m| m.group.len());
+    let size_width = sizes.iter().map(String::len).max().unwrap_or(0);
+
+    items
+        .iter()
+        .zip(names)
+        .zip(sizes)
+        .map(|(((_, entry), name), size)| {
+            let meta = entry.meta();
+            let mut spans = vec![
+                Span::raw(format!(
+                    "{}  {:>lw$} {:<ow$}  {:<gw$}  {:>sw$} {} ",
+                    entry.permissions(),
+                    meta.nlink,
+                    meta.owner,
+                    meta.group,
+                    size,
+                    time::format_ls_time(meta.mtime, now),
+                    lw = link_width,
+                    ow = owner_width,
+                    gw = group_width,
+                    sw = size_width,
+                )),
+                name,
+            ];
+            if let FSEntry::Symlink { target, .. } = entry {
+                spans.push(Span::raw(format!(" -> {}", target)));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Lay names out in columns, filled top to bottom, as wide as `width` allows
+fn format_ls_grid(names: Vec<Span<'static>>, width: usize) -> Vec<Line<'static>> {
+    const GAP: usize = 2;
+    if names.is_empty() {
+        return Vec::new();
+    }
+    let widths: Vec<usize> = names.iter().map(Span::width).collect();
+
+    // Fewest rows whose columns fit in the width
+    let mut layout = (names.len(), vec![widths.iter().copied().max().unwrap_or(0)]);
+    for rows in 1..names.len() {
+        let column_widths: Vec<usize> = widths
+            .chunks(rows)
+            .map(|column| column.iter().copied().max().unwrap_or(0))
+            .collect();
+        let total = column_widths.iter().sum::<usize>() + GAP * (column_widths.len() - 1);
+        if total <= width {
+            layout = (rows, column_widths);
+            break;
+        }
+    }
+    let (rows, column_widths) = layout;
+
+    let mut lines = vec![Vec::new(); rows];
+    for (i, (name, width)) in names.into_iter().zip(&widths).enumerate() {
+        let (column, row) = (i / rows, i % rows);
+        let line: &mut Vec<Span<'static>> = &mut lines[row];
+        line.push(name);
+        let is_last = (column + 1) * rows + row >= widths.len();
+        if !is_last {
+            line.push(Span::raw(" ".repeat(column_widths[column] - width + GAP)));
+        }
+    }
+    lines.into_iter().map(Line::from).collect()
+}
+
+/// Size with a K/M/G suffix, rounded up like `ls -h` (e.g. `3.2K`, `42K`)
+fn human_size(bytes: usize) -> String {
+    if bytes < 1024 {
+        return bytes.to_string();
+    }
+    let mut value = bytes as f64;
+    let mut unit = 'B';
+    for next in ['K', 'M', 'G', 'T'] {
+        value /= 1024.0;
+        unit = next;
+        if value < 1024.0 {
+            break;
+        }
+    }
+    let tenths = (value * 10.0).ceil() / 10.0;
+    if tenths < 10.0 {
+        format!("{:.1}{}", tenths, unit)
+    } else {
+        format!("{}{}", value.ceil(), unit)
+    }
+}
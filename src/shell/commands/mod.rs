@@ -0,0 +1,343 @@
+//! Builtin commands. Each command implements [`Command`] in its own module
+//! and is listed in [`COMMANDS`]; the executor, `help`, `man` and tab
+//! completion all work from that list.
+
+mod cat;
+mod eggs;
+mod exec;
+mod fileops;
+mod info;
+mod ls;
+mod nav;
+mod script;
+mod text;
+mod vars;
+mod walk;
+
+use ratatui::text::Line;
+
+use super::env::Environment;
+use super::history::History;
+use super::VirtualFS;
+use crate::ui;
+
+pub use exec::execute_command;
+
+/// Result of executing a command
+pub enum CommandResult {
+    /// Lines the command printed and how it ended
+    Output {
+        /// Regular output, which is what gets piped and redirected with `>`
+        stdout: Vec<Line<'static>>,
+        /// Error messages, shown on the terminal unless redirected with `2>`
+        stderr: Vec<Line<'static>>,
+        /// Exit status: 0 for success, anything else for failure
+        status: i32,
+    },
+    /// Clear the screen
+    Clear,
+    /// End the session, or the script being run, with an exit status
+    Exit(i32),
+    /// Launch a sub-app (handled by main)
+    AppLaunch(&'static str),
+    /// Open a file in the editor
+    Edit {
+        /// File name as the user typed it
+        name: String,
+        /// Resolved path to save to
+        path: String,
+        content: String,
+    },
+    /// Show lines in the pager
+    Page {
+        /// What is being paged, for the title
+        title: String,
+        lines: Vec<Line<'static>>,
+    },
+    /// Several of the above in the order they happened, from a script or
+    /// function that printed lines and also opened the pager, an app or
+    /// the editor
+    Sequence(Vec<CommandResult>),
+}
+
+impl CommandResult {
+    /// Output and error messages; the command failed if there are any errors
+    pub fn new(stdout: Vec<Line<'static>>, stderr: Vec<Line<'static>>) -> Self {
+        let status = if stderr.is_empty() { 0 } else { 1 };
+        CommandResult::Output { stdout, stderr, status }
+    }
+
+    /// Successful output
+    pub fn output(stdout: Vec<Line<'static>>) -> Self {
+        Self::new(stdout, Vec::new())
+    }
+
+    /// A failure with these error messages, which may be none
+    pub fn failed(stderr: Vec<Line<'static>>) -> Self {
+        CommandResult::Output {
+            stdout: Vec::new(),
+            stderr,
+            status: 1,
+        }
+    }
+
+    /// The same result with another exit status, if it has one
+    pub fn with_status(mut self, new: i32) -> Self {
+        match &mut self {
+            CommandResult::Output { status, .. } => *status = new,
+            CommandResult::Sequence(results) => {
+                let last = results.iter_mut().rev().find_map(|result| match result {
+                    CommandResult::Output { status, .. } => Some(status),
+                    _ => None,
+                });
+                match last {
+                    Some(status) => *status = new,
+                    None if new != 0 => results.push(CommandResult::failed(vec![]).with_status(new)),
+                    None => {}
+                }
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// Exit status; only output can fail
+    pub fn status(&self) -> i32 {
+        match self {
+            CommandResult::Output { status, .. } => *status,
+            CommandResult::Sequence(results) => results
+                .iter()
+                .rev()
+                .find_map(|result| match result {
+                    CommandResult::Output { status, .. } => Some(*status),
+                    _ => None,
+                })
+                .unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// The results one by one, with any sequence taken apart
+    pub fn flatten(self) -> Vec<CommandResult> {
+        match self {
+            CommandResult::Sequence(results) => results.into_iter().flat_map(CommandResult::flatten).collect(),
+            other => vec![other],
+        }
+    }
+}
+
+/// What a command's arguments complete to
+#[derive(Clone, Copy, PartialEq)]
+pub enum ArgKind {
+    Paths,
+    Dirs,
+    Commands,
+    Nothing,
+}
+
+/// Session state a command can read and change
+pub struct Context<'a> {
+    pub fs: &'a mut VirtualFS,
+    pub cwd: &'a mut String,
+    pub env: &'a mut Environment,
+    pub history: &'a mut History,
+    /// Output of the previous pipeline stage, if any
+    pub stdin: Option<Vec<Line<'static>>>,
+    /// Set when the output goes to the terminal rather than a pipe or file
+    pub tty: bool,
+}
+
+/// A builtin command
+pub trait Command: Sync {
+    /// Name the command is run by
+    fn name(&self) -> &'static str;
+
+    /// Other names that run the same command
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Usage, e.g. `cd <dir>`
+    fn synopsis(&self) -> &'static str;
+
+    /// One-line description for `help`
+    fn summary(&self) -> &'static str;
+
+    /// Longer description for `man`
+    fn help(&self) -> &'static str {
+        ""
+    }
+
+    /// Flags and what they do, for `man`, `help` and `--help`
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Command lines and what they do, for `man`
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Hidden commands (the easter eggs) stay out of `help`
+    fn hidden(&self) -> bool {
+        false
+    }
+
+    /// What the command's arguments complete to
+    fn completion(&self) -> ArgKind {
+        ArgKind::Paths
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult;
+}
+
+/// Every builtin
+static COMMANDS: &[&dyn Command] = &[
+    // Files and navigation
+    &ls::Ls,
+    &nav::Cd,
+    &nav::Pwd,
+    &cat::Cat,
+    &cat::Less,
+    &cat::Glow,
+    &cat::Bat,
+    &walk::Tree,
+    &walk::Find,
+    &fileops::Touch,
+    &fileops::Mkdir,
+    &fileops::Cp,
+    &fileops::Mv,
+    &fileops::Rm,
+    &fileops::Ln,
+    &fileops::Nano,
+    // Text processing
+    &text::Grep,
+    &text::Head,
+    &text::Tail,
+    &text::Wc,
+    &text::Sort,
+    &text::Uniq,
+    &text::Cut,
+    &text::Tr,
+    // Session and system
+    &info::Echo,
+    &info::Date,
+    &info::Whoami,
+    &info::Uname,
+    &info::History,
+    &info::Clear,
+    // Variables and aliases
+    &vars::Export,
+    &vars::Unset,
+    &vars::Env,
+    &vars::Set,
+    &vars::Alias,
+    &vars::Unalias,
+    // Scripts
+    &script::Source,
+    &script::Sh,
+    &script::Test,
+    &script::Bracket,
+    &script::True,
+    &script::False,
+    &script::Shift,
+    // Help and leaving
+    &info::Help,
+    &info::Man,
+    &info::Exit,
+    // Easter eggs
+    &eggs::Eastereggs,
+    &eggs::Sudo,
+    &eggs::Hack,
+    &eggs::Vim,
+    &eggs::Emacs,
+    &eggs::Neofetch,
+    &eggs::Cowsay,
+    &eggs::Fortune,
+    &eggs::Matrix,
+    &eggs::Sl,
+];
+
+/// Every builtin, in `help` order
+pub fn all() -> &'static [&'static dyn Command] {
+    COMMANDS
+}
+
+/// Look up a builtin by name or alias
+pub fn find(name: &str) -> Option<&'static dyn Command> {
+    COMMANDS
+        .iter()
+        .copied()
+        .find(|command| command.name() == name || command.aliases().contains(&name))
+}
+
+/// Split arguments into single-letter flags and operands. `--` ends flags.
+fn split_flags<'a>(args: &[&'a str]) -> (Vec<char>, Vec<&'a str>) {
+    let mut flags = Vec::new();
+    let mut operands = Vec::new();
+    let mut only_operands = false;
+    for arg in args {
+        if only_operands || !arg.starts_with('-') || *arg == "-" {
+            operands.push(*arg);
+        } else if *arg == "--" {
+            only_operands = true;
+        } else {
+            flags.extend(arg.chars().skip(1));
+        }
+    }
+    (flags, operands)
+}
+
+/// Options with their values (empty for plain flags), then the operands
+type Parsed<'a> = (Vec<(char, &'a str)>, Vec<&'a str>);
+
+/// Like [`split_flags`], but the letters in `takes_value` consume a value:
+/// the rest of the argument (`-n5`) or the next argument (`-n 5`)
+fn parse_options<'a>(
+    name: &str,
+    args: &[&'a str],
+    takes_value: &str,
+) -> Result<Parsed<'a>, CommandResult> {
+    let mut options = Vec::new();
+    let mut operands = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') || *arg == "-" {
+            operands.push(*arg);
+            continue;
+        }
+        if *arg == "--" {
+            operands.extend(args.by_ref());
+            break;
+        }
+        for (i, flag) in arg.char_indices().skip(1) {
+            if !takes_value.contains(flag) {
+                options.push((flag, ""));
+                continue;
+            }
+            let rest = &arg[i + flag.len_utf8()..];
+            let value = if rest.is_empty() { args.next().copied() } else { Some(rest) };
+            match value {
+                Some(value) => options.push((flag, value)),
+                None => {
+                    return Err(error(&format!(
+                        "{}: option requires an argument -- '{}'",
+                        name, flag
+                    )));
+                }
+            }
+            break;
+        }
+    }
+    Ok((options, operands))
+}
+
+/// Output for a command that reports nothing on success
+fn file_op_result(errors: Vec<Line<'static>>) -> CommandResult {
+    CommandResult::new(vec![], errors)
+}
+
+/// A failed command with a single error message
+fn error(message: &str) -> CommandResult {
+    CommandResult::failed(vec![ui::error_text(message)])
+}
@@ -0,0 +1,96 @@
+//! `cd` and `pwd`
+
+use ratatui::text::Line;
+
+use super::{error, ArgKind, Command, CommandResult, Context};
+use crate::shell::filesystem::HOME;
+use crate::shell::{FSEntry, VirtualFS};
+
+pub struct Cd;
+
+impl Command for Cd {
+    fn name(&self) -> &'static str {
+        "cd"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "cd [dir]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Change directory"
+    }
+
+    fn help(&self) -> &'static str {
+        "Make dir the working directory, or go home with no argument. \
+         Symlinks are resolved, so the new directory is always a real path."
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("cd blog/2026", "go to this year's posts"),
+            ("cd ..", "go up a directory"),
+        ]
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Dirs
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        change_dir(args, ctx.fs, ctx.cwd)
+    }
+}
+
+pub struct Pwd;
+
+impl Command for Pwd {
+    fn name(&self) -> &'static str {
+        "pwd"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "pwd"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Print working directory"
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, _args: &[&str], ctx: &mut Context) -> CommandResult {
+        CommandResult::output(vec![Line::from(ctx.cwd.clone())])
+    }
+}
+
+fn change_dir(args: &[&str], fs: &VirtualFS, cwd: &mut String) -> CommandResult {
+    let target = args.first().unwrap_or(&"~");
+
+    // Symlinks are followed, so the new directory is always a real path
+    let new_path = match *target {
+        "" => HOME.to_string(),
+        path => match fs.follow(&fs.resolve_path(path, cwd)) {
+            Ok(path) => path,
+            Err(reason) => {
+                return error(&format!(
+                    "cd: {}: {}",
+                    reason.to_lowercase(),
+                    target
+                ));
+            }
+        },
+    };
+
+    // Check if directory exists
+    match fs.get(&new_path) {
+        Some(FSEntry::Directory { .. }) => {
+            *cwd = new_path;
+            CommandResult::output(vec![])
+        }
+        Some(_) => error(&format!("cd: not a directory: {}", target)),
+        None => error(&format!("cd: no such file or directory: {}", target)),
+    }
+}
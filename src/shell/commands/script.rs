@@ -0,0 +1,362 @@
+//! Scripts - `source`, `sh`, and the commands scripts are written with
+
+use super::exec::{is_runnable, merge, run_script};
+use super::{error, execute_command, ArgKind, Command, CommandResult, Context};
+use crate::shell::FSEntry;
+use crate::ui;
+
+pub struct Source;
+
+impl Command for Source {
+    fn name(&self) -> &'static str {
+        "source"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["."]
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "source <file> [arg...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Run a file's commands in this shell"
+    }
+
+    fn help(&self) -> &'static str {
+        "Run the file's commands as if they were typed at the prompt, so \
+         the variables, aliases and functions it defines stay defined. Any \
+         arguments become $1, $2 and so on while it runs. Text after a # \
+         that starts a word is a comment. ~/.profile is sourced like this \
+         at login.\n\n\
+         Unlike sh, `exit` in a sourced file ends the session."
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[("source ~/.profile", "apply changes to the profile without logging in again")]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let Some((file, args)) = args.split_first() else {
+            return error("source: filename argument required");
+        };
+        let script = match read_script("source", file, ctx) {
+            Ok(script) => script,
+            Err(e) => return e,
+        };
+
+        if let Err(e) = ctx.env.enter() {
+            return error(&format!("source: {}: {}", file, e));
+        }
+        let saved = (!args.is_empty())
+            .then(|| ctx.env.set_positional(None, args.iter().map(ToString::to_string).collect()));
+        let results = execute_command(&script, ctx.fs, ctx.cwd, ctx.env, ctx.history);
+        if let Some(saved) = saved {
+            ctx.env.restore_positional(saved);
+        }
+        ctx.env.leave();
+        merge(results).with_status(ctx.env.status())
+    }
+}
+
+pub struct Sh;
+
+impl Command for Sh {
+    fn name(&self) -> &'static str {
+        "sh"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["rsh", "bash"]
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "sh [-c command | file] [arg...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Run a script"
+    }
+
+    fn help(&self) -> &'static str {
+        "Run a script file, a command string, or the lines piped in. The \
+         script gets its own copy of the session: the variables it sets \
+         and directories it changes to are forgotten when it ends. Files \
+         whose first line is #!/bin/rsh can also be run directly, as in \
+         ./script.sh.\n\n\
+         Scripts can use if, for, while and until, functions, $1 to $9 \
+         for their arguments, $# for how many there are, $@ for all of \
+         them, $? for the last command's status, and `exit n`:\n\n\
+         for file in ~/*.md; do\n\
+         \x20   if [ -s $file ]; then echo $file; head -3 $file; fi\n\
+         done\n\n\
+         Error messages go to the terminal even when the output is piped \
+         or redirected. Send them to a file with 2> file, along with the \
+         output with 2>&1, or away with 2>/dev/null. Redirections after \
+         a loop, if or { group; } apply to everything it runs, as in \
+         for f in ~/*.md; do head -1 $f; done > titles."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-c command", "run the command string; any args after it start at $0")]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("sh ~/bin/tour.sh", "take the tour of this site"),
+            ("sh -c 'echo $1' sh hello", "print hello"),
+        ]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        match args {
+            [] => match ctx.stdin.take() {
+                Some(lines) => {
+                    let script: Vec<String> = lines.iter().map(ui::line_to_string).collect();
+                    run_script("sh", &script.join("\n"), &[], ctx)
+                }
+                None => error("sh: nothing to run; give it a script file or -c command"),
+            },
+            ["-c"] => error("sh: -c: option requires an argument"),
+            ["-c", command, rest @ ..] => {
+                let (name, args) = rest.split_first().unwrap_or((&"sh", &[]));
+                run_script(name, command, args, ctx)
+            }
+            [file, args @ ..] => match read_script("sh", file, ctx) {
+                Ok(script) => run_script(file, &script, args, ctx),
+                Err(e) => e,
+            },
+        }
+    }
+}
+
+/// The contents of a script file
+fn read_script(command: &str, file: &str, ctx: &Context) -> Result<String, CommandResult> {
+    let path = ctx.fs.resolve_path(file, ctx.cwd);
+    match ctx.fs.stat(&path) {
+        Ok(FSEntry::File { content, .. }) => Ok(content.clone()),
+        Ok(FSEntry::Directory { .. }) => Err(error(&format!("{}: {}: Is a directory", command, file))),
+        Ok(_) => Err(error(&format!("{}: {}: cannot execute binary file", command, file))),
+        Err(reason) => Err(error(&format!("{}: {}: {}", command, file, reason))),
+    }
+}
+
+pub struct Test;
+
+impl Command for Test {
+    fn name(&self) -> &'static str {
+        "test"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "test <expression>"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Check files, strings and numbers"
+    }
+
+    fn help(&self) -> &'static str {
+        "Succeed if the expression is true and fail if it is not, printing \
+         nothing, for use with if, while, && and ||. `[ expression ]` is \
+         the same with a closing bracket. A single string is true when it \
+         is not empty, and ! in front of an expression negates it."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-e file", "file exists"),
+            ("-f file", "file exists and is a regular file or executable"),
+            ("-d file", "file exists and is a directory"),
+            ("-x file", "file is an executable or a #!/bin/rsh script"),
+            ("-L file", "file is a symbolic link"),
+            ("-s file", "file exists and is not empty"),
+            ("-z string", "string is empty"),
+            ("-n string", "string is not empty"),
+            ("a = b, a != b", "the strings are equal, or not"),
+            ("m -eq n", "the numbers are equal; also -ne -lt -le -gt -ge"),
+        ]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("test -d ~/blog && ls ~/blog", "list the blog if there is one"),
+            ("[ $# -gt 0 ] || echo no arguments", "check a script's argument count"),
+        ]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        evaluate("test", args, ctx)
+    }
+}
+
+pub struct Bracket;
+
+impl Command for Bracket {
+    fn name(&self) -> &'static str {
+        "["
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "[ <expression> ]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Check files, strings and numbers (see test)"
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        match args.split_last() {
+            Some((&"]", expression)) => evaluate("[", expression, ctx),
+            _ => error("[: missing `]'").with_status(2),
+        }
+    }
+}
+
+/// Run a `test` expression: no output, success when it holds, and
+/// status 2 when it makes no sense
+fn evaluate(command: &str, args: &[&str], ctx: &Context) -> CommandResult {
+    match expression(args, ctx) {
+        Ok(true) => CommandResult::output(vec![]),
+        Ok(false) => CommandResult::failed(vec![]),
+        Err(e) => error(&format!("{}: {}", command, e)).with_status(2),
+    }
+}
+
+fn expression(args: &[&str], ctx: &Context) -> Result<bool, String> {
+    match args {
+        [] => Ok(false),
+        [string] => Ok(!string.is_empty()),
+        ["!", rest @ ..] => expression(rest, ctx).map(|holds| !holds),
+        ["-z", string] => Ok(string.is_empty()),
+        ["-n", string] => Ok(!string.is_empty()),
+        [operator @ ("-e" | "-f" | "-d" | "-x" | "-L" | "-h" | "-s"), file] => {
+            let path = ctx.fs.resolve_path(file, ctx.cwd);
+            Ok(match *operator {
+                "-L" | "-h" => matches!(ctx.fs.get(&path), Some(FSEntry::Symlink { .. })),
+                operator => match ctx.fs.stat(&path) {
+                    Ok(entry) => match operator {
+                        "-f" => matches!(
+                            entry,
+                            FSEntry::File { .. } | FSEntry::Binary { .. } | FSEntry::Executable { .. }
+                        ),
+                        "-d" => matches!(entry, FSEntry::Directory { .. }),
+                        "-x" => is_runnable(entry),
+                        "-s" => entry.meta().size > 0,
+                        _ => true,
+                    },
+                    Err(_) => false,
+                },
+            })
+        }
+        [operator, _] => Err(format!("{}: unary operator expected", operator)),
+        [a, "=" | "==", b] => Ok(a == b),
+        [a, "!=", b] => Ok(a != b),
+        [a, operator @ ("-eq" | "-ne" | "-lt" | "-le" | "-gt" | "-ge"), b] => {
+            let (a, b) = (integer(a)?, integer(b)?);
+            Ok(match *operator {
+                "-eq" => a == b,
+                "-ne" => a != b,
+                "-lt" => a < b,
+                "-le" => a <= b,
+                "-gt" => a > b,
+                _ => a >= b,
+            })
+        }
+        [_, operator, _] => Err(format!("{}: binary operator expected", operator)),
+        _ => Err("too many arguments".to_string()),
+    }
+}
+
+fn integer(arg: &str) -> Result<i64, String> {
+    arg.trim()
+        .parse()
+        .map_err(|_| format!("{}: integer expression expected", arg))
+}
+
+pub struct True;
+
+impl Command for True {
+    fn name(&self) -> &'static str {
+        "true"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "true"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Do nothing, successfully"
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, _args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::output(vec![])
+    }
+}
+
+pub struct False;
+
+impl Command for False {
+    fn name(&self) -> &'static str {
+        "false"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "false"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Do nothing, unsuccessfully"
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, _args: &[&str], _ctx: &mut Context) -> CommandResult {
+        CommandResult::failed(vec![])
+    }
+}
+
+pub struct Shift;
+
+impl Command for Shift {
+    fn name(&self) -> &'static str {
+        "shift"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "shift [n]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Drop the first arguments of a script or function"
+    }
+
+    fn help(&self) -> &'static str {
+        "Drop the first n positional parameters (one by default), so $2 \
+         becomes $1 and so on."
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let n = match args.first().map(|n| n.parse()) {
+            None => 1,
+            Some(Ok(n)) => n,
+            Some(Err(_)) => return error(&format!("shift: {}: numeric argument required", args[0])),
+        };
+        if ctx.env.shift(n) {
+            CommandResult::output(vec![])
+        } else {
+            error("shift: shift count out of range")
+        }
+    }
+}
@@ -0,0 +1,1065 @@
+//! Text tools - `grep`, `head`, `tail`, `wc`, `sort`, `uniq`, `cut` and
+//! `tr` - over files or the previous pipeline stage's output
+
+use std::cmp::Ordering;
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use super::{error, parse_options, ArgKind, Command, CommandResult, Context};
+use crate::shell::filesystem::child_path;
+use crate::shell::{FSEntry, VirtualFS};
+use crate::theme::Theme;
+use crate::ui;
+
+/// Lines `head` and `tail` show by default
+const DEFAULT_LINES: usize = 10;
+
+/// Text a tool reads: a file, or stdin when there is no name
+struct Input {
+    name: Option<String>,
+    text: String,
+}
+
+/// Read each file operand, or stdin when there are none. Files that can't
+/// be read become error lines and the rest are still read.
+fn read_inputs(
+    tool: &str,
+    files: &[&str],
+    ctx: &mut Context,
+) -> Result<(Vec<Input>, Vec<Line<'static>>), CommandResult> {
+    if files.is_empty() {
+        return match ctx.stdin.take() {
+            Some(lines) => Ok((vec![stdin_input(&lines)], Vec::new())),
+            None => Err(error(&format!("{}: missing operand", tool))),
+        };
+    }
+
+    let mut inputs = Vec::new();
+    let mut errors = Vec::new();
+    for file in files {
+        let path = ctx.fs.resolve_path(file, ctx.cwd);
+        let reason = match ctx.fs.stat(&path) {
+            Ok(FSEntry::File { content, .. }) => {
+                inputs.push(Input {
+                    name: Some(file.to_string()),
+                    text: content.clone(),
+                });
+                continue;
+            }
+            Ok(FSEntry::Directory { .. }) => "Is a directory".to_string(),
+            Ok(FSEntry::Binary { .. }) => "binary file".to_string(),
+            Ok(_) => "Is an executable".to_string(),
+            Err(reason) => reason,
+        };
+        errors.push(ui::error_text(&format!("{}: {}: {}", tool, file, reason)));
+    }
+    Ok((inputs, errors))
+}
+
+fn stdin_input(lines: &[Line<'static>]) -> Input {
+    Input {
+        name: None,
+        text: lines
+            .iter()
+            .map(|line| ui::line_to_string(line) + "\n")
+            .collect(),
+    }
+}
+
+fn plain(lines: impl IntoIterator<Item = String>) -> Vec<Line<'static>> {
+    lines.into_iter().map(Line::from).collect()
+}
+
+fn invalid_option(tool: &str, flag: char) -> CommandResult {
+    error(&format!("{}: invalid option -- '{}'", tool, flag))
+}
+
+pub struct Grep;
+
+impl Command for Grep {
+    fn name(&self) -> &'static str {
+        "grep"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "grep [-i] [-n] [-r] [-v] [-c] [-l] <pattern> [file...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Print lines containing a pattern"
+    }
+
+    fn help(&self) -> &'static str {
+        "Print the lines of each file, or of the previous pipeline stage, \
+         that contain the pattern as plain text. Matches are highlighted. \
+         With more than one file, each line starts with its file's name."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-i", "ignore case"),
+            ("-n", "show line numbers"),
+            ("-r", "search directories recursively (the current one by default)"),
+            ("-v", "print the lines that don't match"),
+            ("-c", "print how many lines matched instead"),
+            ("-l", "print only the names of files with a match"),
+        ]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("grep -rn rust", "every mention of rust below here, with line numbers"),
+            ("ls | grep -i md", "names containing md in any case"),
+        ]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let (options, operands) = match parse_options("grep", args, "") {
+            Ok(parsed) => parsed,
+            Err(e) => return e,
+        };
+        let (mut ignore_case, mut numbers, mut recursive, mut invert, mut count, mut names_only) =
+            (false, false, false, false, false, false);
+        for (flag, _) in options {
+            match flag {
+                'i' => ignore_case = true,
+                'n' => numbers = true,
+                'r' | 'R' => recursive = true,
+                'v' => invert = true,
+                'c' => count = true,
+                'l' => names_only = true,
+                other => return invalid_option("grep", other).with_status(2),
+            }
+        }
+        let Some((pattern, files)) = operands.split_first() else {
+            return error("grep: usage: grep [-i] [-n] [-r] [-v] [-c] [-l] <pattern> [file...]").with_status(2);
+        };
+
+        let (inputs, errors) = if recursive {
+            walk_inputs(files, ctx.fs, ctx.cwd)
+        } else {
+            match read_inputs("grep", files, ctx) {
+                Ok(read) => read,
+                Err(e) => return e,
+            }
+        };
+        let show_names = recursive || files.len() > 1;
+
+        let mut output = Vec::new();
+        let mut selected = 0;
+        for input in &inputs {
+            let name = input.name.as_deref().unwrap_or("(standard input)");
+            let mut matched = 0;
+            for (i, line) in input.text.lines().enumerate() {
+                let ranges = find_matches(line, pattern, ignore_case);
+                if (pattern.is_empty() || !ranges.is_empty()) == invert {
+                    continue;
+                }
+                matched += 1;
+                if count || names_only {
+                    continue;
+                }
+                let mut spans = Vec::new();
+                if show_names {
+                    spans.push(Span::styled(name.to_string(), Style::default().fg(Theme::ACCENT)));
+                    spans.push(Span::styled(":", Style::default().fg(Theme::MUTED)));
+                }
+                if numbers {
+                    spans.push(Span::styled((i + 1).to_string(), Style::default().fg(Theme::SUCCESS)));
+                    spans.push(Span::styled(":", Style::default().fg(Theme::MUTED)));
+                }
+                spans.extend(highlight_matches(line, if invert { &[] } else { &ranges }));
+                output.push(Line::from(spans));
+            }
+            if names_only {
+                if matched > 0 {
+                    output.push(Line::from(Span::styled(name.to_string(), Style::default().fg(Theme::ACCENT))));
+                }
+            } else if count {
+                output.push(Line::from(if show_names {
+                    format!("{}:{}", name, matched)
+                } else {
+                    matched.to_string()
+                }));
+            }
+            selected += matched;
+        }
+
+        // Like grep's exit status: 2 for trouble, 1 for finding nothing
+        let status = if !errors.is_empty() {
+            2
+        } else if selected == 0 {
+            1
+        } else {
+            0
+        };
+        CommandResult::new(output, errors).with_status(status)
+    }
+}
+
+/// Every file under the operands, for `grep -r`. Symlinks inside the
+/// directories are not followed.
+fn walk_inputs(roots: &[&str], fs: &VirtualFS, cwd: &str) -> (Vec<Input>, Vec<Line<'static>>) {
+    let mut inputs = Vec::new();
+    let mut errors = Vec::new();
+    if roots.is_empty() {
+        walk(fs, cwd, "", &mut inputs);
+    }
+    for root in roots {
+        let path = fs.resolve_path(root, cwd);
+        match fs.stat(&path) {
+            Ok(FSEntry::File { content, .. }) => inputs.push(Input {
+                name: Some(root.to_string()),
+                text: content.clone(),
+            }),
+            Ok(FSEntry::Directory { .. }) => {
+                let path = fs.follow(&path).unwrap_or(path);
+                walk(fs, &path, root.trim_end_matches('/'), &mut inputs);
+            }
+            Ok(_) => {}
+            Err(reason) => errors.push(ui::error_text(&format!("grep: {}: {}", root, reason))),
+        }
+    }
+    (inputs, errors)
+}
+
+fn walk(fs: &VirtualFS, path: &str, display: &str, inputs: &mut Vec<Input>) {
+    for (name, entry) in fs.list_dir(path).unwrap_or_default() {
+        let shown = if display.is_empty() { name.clone() } else { format!("{}/{}", display, name) };
+        match entry {
+            FSEntry::File { content, .. } => inputs.push(Input {
+                name: Some(shown),
+                text: content.clone(),
+            }),
+            FSEntry::Directory { .. } => walk(fs, &child_path(path, &name), &shown, inputs),
+            _ => {}
+        }
+    }
+}
+
+/// Byte ranges where `pattern` occurs in `line`. Case folding is ASCII-only
+/// so the ranges line up with the original text.
+fn find_matches(line: &str, pattern: &str, ignore_case: bool) -> Vec<(usize, usize)> {
+    if pattern.is_empty() {
+        return Vec::new();
+    }
+    let ranges = |haystack: &str, needle: &str| -> Vec<(usize, usize)> {
+        haystack
+            .match_indices(needle)
+            .map(|(start, m)| (start, start + m.len()))
+            .collect()
+    };
+    if ignore_case {
+        ranges(&line.to_ascii_lowercase(), &pattern.to_ascii_lowercase())
+    } else {
+        ranges(line, pattern)
+    }
+}
+
+/// `line` split into spans with the matched ranges highlighted
+fn highlight_matches(line: &str, ranges: &[(usize, usize)]) -> Vec<Span<'static>> {
+    let mark = Style::default()
+        .fg(Theme::WARNING)
+        .add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for &(start, end) in ranges {
+        if start > pos {
+            spans.push(Span::raw(line[pos..start].to_string()));
+        }
+        spans.push(Span::styled(line[start..end].to_string(), mark));
+        pos = end;
+    }
+    if pos < line.len() {
+        spans.push(Span::raw(line[pos..].to_string()));
+    }
+    spans
+}
+
+/// `-n N` or the old `-N` form for `head` and `tail`, and the file operands
+fn lines_option<'a>(tool: &str, args: &[&'a str]) -> Result<(Option<&'a str>, Vec<&'a str>), CommandResult> {
+    let mut count = None;
+    let mut rest = args;
+    if let Some((first, others)) = args.split_first()
+        && let Some(n) = first.strip_prefix('-')
+        && !n.is_empty()
+        && n.bytes().all(|b| b.is_ascii_digit())
+    {
+        count = Some(n);
+        rest = others;
+    }
+    let (options, files) = parse_options(tool, rest, "n")?;
+    for (flag, value) in options {
+        match flag {
+            'n' => count = Some(value),
+            other => return Err(invalid_option(tool, other)),
+        }
+    }
+    Ok((count, files))
+}
+
+fn parse_count(tool: &str, value: &str) -> Result<usize, CommandResult> {
+    value
+        .parse()
+        .map_err(|_| error(&format!("{}: invalid number of lines: '{}'", tool, value)))
+}
+
+/// Run `select` over each input, with `==> name <==` headers when there
+/// is more than one
+fn per_input(inputs: &[Input], select: impl Fn(&str) -> Vec<String>) -> Vec<Line<'static>> {
+    let mut output = Vec::new();
+    for (i, input) in inputs.iter().enumerate() {
+        if inputs.len() > 1 {
+            if i > 0 {
+                output.push(Line::from(""));
+            }
+            let name = input.name.as_deref().unwrap_or("standard input");
+            output.push(Line::from(format!("==> {} <==", name)));
+        }
+        output.extend(plain(select(&input.text)));
+    }
+    output
+}
+
+pub struct Head;
+
+impl Command for Head {
+    fn name(&self) -> &'static str {
+        "head"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "head [-n count] [file...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Print the first lines of files"
+    }
+
+    fn help(&self) -> &'static str {
+        "Print the first 10 lines of each file, or of the previous pipeline \
+         stage. -5 is short for -n 5."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-n count", "print count lines instead of 10")]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[("head -3 README.md", "the first three lines of the README")]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let (count, files) = match lines_option("head", args) {
+            Ok(parsed) => parsed,
+            Err(e) => return e,
+        };
+        let count = match count.map(|n| parse_count("head", n)).transpose() {
+            Ok(count) => count.unwrap_or(DEFAULT_LINES),
+            Err(e) => return e,
+        };
+        let (inputs, errors) = match read_inputs("head", &files, ctx) {
+            Ok(read) => read,
+            Err(e) => return e,
+        };
+        let output = per_input(&inputs, |text| text.lines().take(count).map(str::to_string).collect());
+        CommandResult::new(output, errors)
+    }
+}
+
+pub struct Tail;
+
+impl Command for Tail {
+    fn name(&self) -> &'static str {
+        "tail"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "tail [-n [+]count] [file...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Print the last lines of files"
+    }
+
+    fn help(&self) -> &'static str {
+        "Print the last 10 lines of each file, or of the previous pipeline \
+         stage."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-n count", "print the last count lines instead of 10"),
+            ("-n +line", "print everything from that line on"),
+        ]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[("history | tail -n 5", "the five most recent commands")]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let (count, files) = match lines_option("tail", args) {
+            Ok(parsed) => parsed,
+            Err(e) => return e,
+        };
+        // `+N` counts from the start, anything else from the end
+        let (from_start, count) = match count {
+            Some(n) => match n.strip_prefix('+') {
+                Some(n) => (true, parse_count("tail", n)),
+                None => (false, parse_count("tail", n)),
+            },
+            None => (false, Ok(DEFAULT_LINES)),
+        };
+        let count = match count {
+            Ok(count) => count,
+            Err(e) => return e,
+        };
+        let (inputs, errors) = match read_inputs("tail", &files, ctx) {
+            Ok(read) => read,
+            Err(e) => return e,
+        };
+        let output = per_input(&inputs, |text| {
+            let lines: Vec<&str> = text.lines().collect();
+            let skip = if from_start {
+                count.saturating_sub(1)
+            } else {
+                lines.len().saturating_sub(count)
+            };
+            lines.into_iter().skip(skip).map(str::to_string).collect()
+        });
+        CommandResult::new(output, errors)
+    }
+}
+
+pub struct Wc;
+
+impl Command for Wc {
+    fn name(&self) -> &'static str {
+        "wc"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "wc [-l] [-w] [-c] [file...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Count lines, words and bytes"
+    }
+
+    fn help(&self) -> &'static str {
+        "Print the line, word and byte counts of each file, or of the \
+         previous pipeline stage, with a total when there are several \
+         files. Options pick which counts to print."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-l", "lines"), ("-w", "words"), ("-c", "bytes")]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[("wc -w blog/2026/*.md", "words in each blog post, and in all of them")]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let (options, files) = match parse_options("wc", args, "") {
+            Ok(parsed) => parsed,
+            Err(e) => return e,
+        };
+        let (mut lines, mut words, mut bytes) = (false, false, false);
+        for (flag, _) in options {
+            match flag {
+                'l' => lines = true,
+                'w' => words = true,
+                'c' => bytes = true,
+                other => return invalid_option("wc", other),
+            }
+        }
+        if !(lines || words || bytes) {
+            (lines, words, bytes) = (true, true, true);
+        }
+        let (inputs, errors) = match read_inputs("wc", &files, ctx) {
+            Ok(read) => read,
+            Err(e) => return e,
+        };
+
+        let mut rows: Vec<(Vec<usize>, Option<String>)> = inputs
+            .iter()
+            .map(|input| {
+                let counts = [
+                    // Newlines, like coreutils: a last line without one is not counted
+                    (lines, input.text.matches('\n').count()),
+                    (words, input.text.split_whitespace().count()),
+                    (bytes, input.text.len()),
+                ];
+                let counts = counts.iter().filter(|(on, _)| *on).map(|(_, n)| *n).collect();
+                (counts, input.name.clone())
+            })
+            .collect();
+        if rows.len() > 1 {
+            let columns = rows[0].0.len();
+            let totals = (0..columns).map(|c| rows.iter().map(|(counts, _)| counts[c]).sum()).collect();
+            rows.push((totals, Some("total".to_string())));
+        }
+
+        // A lone count is printed bare; otherwise columns line up
+        let single = rows.len() == 1 && rows[0].0.len() == 1;
+        let width = if single {
+            0
+        } else {
+            rows.iter()
+                .flat_map(|(counts, _)| counts.iter().map(|n| n.to_string().len()))
+                .max()
+                .unwrap_or(1)
+        };
+        let output = rows.into_iter().map(|(counts, name)| {
+            let mut row: Vec<String> = counts.iter().map(|n| format!("{:>width$}", n)).collect();
+            row.extend(name);
+            row.join(" ")
+        });
+        CommandResult::new(plain(output), errors)
+    }
+}
+
+pub struct Sort;
+
+impl Command for Sort {
+    fn name(&self) -> &'static str {
+        "sort"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "sort [-r] [-n] [-u] [file...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Sort lines"
+    }
+
+    fn help(&self) -> &'static str {
+        "Print the lines of all the files, or of the previous pipeline \
+         stage, in order."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-r", "reverse the order"),
+            ("-n", "compare by the number each line starts with"),
+            ("-u", "drop lines that compare equal to the one before"),
+        ]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[("history | cut -c 8- | sort | uniq -c | sort -rn", "your most used commands first")]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let (options, files) = match parse_options("sort", args, "") {
+            Ok(parsed) => parsed,
+            Err(e) => return e,
+        };
+        let (mut reverse, mut numeric, mut unique) = (false, false, false);
+        for (flag, _) in options {
+            match flag {
+                'r' => reverse = true,
+                'n' => numeric = true,
+                'u' => unique = true,
+                other => return invalid_option("sort", other),
+            }
+        }
+        let (inputs, errors) = match read_inputs("sort", &files, ctx) {
+            Ok(read) => read,
+            Err(e) => return e,
+        };
+
+        let key = |a: &str, b: &str| {
+            if numeric {
+                leading_number(a).total_cmp(&leading_number(b))
+            } else {
+                a.cmp(b)
+            }
+        };
+        let mut lines: Vec<&str> = inputs.iter().flat_map(|input| input.text.lines()).collect();
+        // Numbers that tie fall back to comparing the whole line
+        lines.sort_by(|a, b| key(a, b).then_with(|| a.cmp(b)));
+        if reverse {
+            lines.reverse();
+        }
+        if unique {
+            lines.dedup_by(|a, b| key(a, b) == Ordering::Equal);
+        }
+        CommandResult::new(plain(lines.into_iter().map(str::to_string)), errors)
+    }
+}
+
+/// The number a line starts with, or 0 when it doesn't start with one
+fn leading_number(line: &str) -> f64 {
+    let text = line.trim_start();
+    let end = text
+        .char_indices()
+        .take_while(|&(i, c)| c.is_ascii_digit() || c == '.' || (i == 0 && matches!(c, '-' | '+')))
+        .last()
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    text[..end].parse().unwrap_or(0.0)
+}
+
+pub struct Uniq;
+
+impl Command for Uniq {
+    fn name(&self) -> &'static str {
+        "uniq"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "uniq [-c] [file]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Collapse repeated lines"
+    }
+
+    fn help(&self) -> &'static str {
+        "Print the file, or the previous pipeline stage, with runs of \
+         identical lines collapsed into one. Only adjacent lines are \
+         compared, so sort first to collapse them all."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-c", "prefix each line with how many times it occurred")]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[("sort words | uniq -c", "how often each line of words appears")]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let (options, files) = match parse_options("uniq", args, "") {
+            Ok(parsed) => parsed,
+            Err(e) => return e,
+        };
+        let mut count = false;
+        for (flag, _) in options {
+            match flag {
+                'c' => count = true,
+                other => return invalid_option("uniq", other),
+            }
+        }
+        if let [_, extra, ..] = files.as_slice() {
+            return error(&format!("uniq: extra operand '{}'", extra));
+        }
+        let (inputs, errors) = match read_inputs("uniq", &files, ctx) {
+            Ok(read) => read,
+            Err(e) => return e,
+        };
+
+        let mut runs: Vec<(usize, &str)> = Vec::new();
+        for line in inputs.iter().flat_map(|input| input.text.lines()) {
+            match runs.last_mut() {
+                Some((n, last)) if *last == line => *n += 1,
+                _ => runs.push((1, line)),
+            }
+        }
+        let output = runs.into_iter().map(|(n, line)| {
+            if count {
+                format!("{:>7} {}", n, line)
+            } else {
+                line.to_string()
+            }
+        });
+        CommandResult::new(plain(output), errors)
+    }
+}
+
+pub struct Cut;
+
+impl Command for Cut {
+    fn name(&self) -> &'static str {
+        "cut"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "cut -f list [-d delim] [file...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Print selected fields of each line"
+    }
+
+    fn help(&self) -> &'static str {
+        "Print parts of each line of the files, or of the previous pipeline \
+         stage. A list is numbers and ranges separated by commas, counting \
+         from 1: 1,3 or 2-4 or 3- or -2."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-f list", "fields, split at the delimiter"),
+            ("-d delim", "the field delimiter (a tab by default)"),
+            ("-c list", "characters"),
+        ]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("cut -d= -f1 .profile", "the names set in .profile"),
+            ("ls -l | cut -c 1-10", "just the permissions"),
+        ]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let (options, files) = match parse_options("cut", args, "dfc") {
+            Ok(parsed) => parsed,
+            Err(e) => return e,
+        };
+        let mut delimiter = '\t';
+        let mut fields = None;
+        let mut chars = None;
+        for (flag, value) in options {
+            match flag {
+                'd' => {
+                    let mut value_chars = value.chars();
+                    match (value_chars.next(), value_chars.next()) {
+                        (Some(c), None) => delimiter = c,
+                        _ => return error("cut: the delimiter must be a single character"),
+                    }
+                }
+                'f' => fields = Some(value),
+                'c' => chars = Some(value),
+                other => return invalid_option("cut", other),
+            }
+        }
+        let (list, by_chars) = match (fields, chars) {
+            (Some(list), None) => (list, false),
+            (None, Some(list)) => (list, true),
+            (Some(_), Some(_)) => return error("cut: only one type of list may be specified"),
+            (None, None) => return error("cut: you must specify a list of characters or fields"),
+        };
+        let Some(ranges) = parse_list(list) else {
+            return error(&format!("cut: invalid list '{}'", list));
+        };
+        let selected = |n: usize| ranges.iter().any(|&(from, to)| from <= n && n <= to);
+
+        let (inputs, errors) = match read_inputs("cut", &files, ctx) {
+            Ok(read) => read,
+            Err(e) => return e,
+        };
+        let output = inputs.iter().flat_map(|input| input.text.lines()).map(|line| {
+            if by_chars {
+                line.chars()
+                    .enumerate()
+                    .filter(|(i, _)| selected(i + 1))
+                    .map(|(_, c)| c)
+                    .collect()
+            } else if !line.contains(delimiter) {
+                // Lines without the delimiter pass through whole, as in cut
+                line.to_string()
+            } else {
+                let parts: Vec<&str> = line
+                    .split(delimiter)
+                    .enumerate()
+                    .filter(|(i, _)| selected(i + 1))
+                    .map(|(_, part)| part)
+                    .collect();
+                parts.join(&delimiter.to_string())
+            }
+        });
+        CommandResult::new(plain(output), errors)
+    }
+}
+
+/// `1,3-5,7-` as inclusive ranges counting from 1
+fn parse_list(list: &str) -> Option<Vec<(usize, usize)>> {
+    list.split(',')
+        .map(|part| {
+            let number = |s: &str| s.parse::<usize>().ok().filter(|n| *n > 0);
+            match part.split_once('-') {
+                Some(("", "")) => None,
+                Some((from, "")) => Some((number(from)?, usize::MAX)),
+                Some(("", to)) => Some((1, number(to)?)),
+                Some((from, to)) => Some((number(from)?, number(to)?)),
+                None => number(part).map(|n| (n, n)),
+            }
+        })
+        .collect()
+}
+
+pub struct Tr;
+
+impl Command for Tr {
+    fn name(&self) -> &'static str {
+        "tr"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "tr [-d] [-s] <set1> [set2]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Translate or delete characters"
+    }
+
+    fn help(&self) -> &'static str {
+        "Copy the previous pipeline stage's output, replacing each character \
+         of set1 with the one at the same place in set2. Sets may use ranges \
+         like a-z, classes like [:upper:], and \\n or \\t."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-d", "delete the characters in set1 instead"),
+            ("-s", "squeeze runs of a repeated character from the last set into one"),
+        ]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("echo hello | tr a-z A-Z", "HELLO"),
+            ("cat README.md | tr -s ' ' '\\n'", "one word per line"),
+        ]
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let (options, operands) = match parse_options("tr", args, "") {
+            Ok(parsed) => parsed,
+            Err(e) => return e,
+        };
+        let (mut delete, mut squeeze) = (false, false);
+        for (flag, _) in options {
+            match flag {
+                'd' => delete = true,
+                's' => squeeze = true,
+                other => return invalid_option("tr", other),
+            }
+        }
+        let (set1, set2) = match operands.as_slice() {
+            [] => return error("tr: missing operand"),
+            [set1] if !delete && !squeeze => {
+                return error(&format!("tr: missing operand after '{}'", set1));
+            }
+            [set1] => (expand_set(set1), None),
+            [set1, set2] => (expand_set(set1), Some(expand_set(set2))),
+            [_, _, extra, ..] => return error(&format!("tr: extra operand '{}'", extra)),
+        };
+        let Some(stdin) = ctx.stdin.take() else {
+            return error("tr: no input (pipe text into tr)");
+        };
+        let text = stdin_input(&stdin).text;
+
+        let mut out: String = if delete {
+            text.chars().filter(|c| !set1.contains(c)).collect()
+        } else if let Some(set2) = &set2 {
+            text.chars()
+                .map(|c| match set1.iter().position(|s| *s == c) {
+                    // A short set2 is stretched with its last character
+                    Some(i) => set2.get(i).or(set2.last()).copied().unwrap_or(c),
+                    None => c,
+                })
+                .collect()
+        } else {
+            text
+        };
+        if squeeze {
+            let squeezed = set2.as_ref().unwrap_or(&set1);
+            out = squeeze_runs(&out, squeezed);
+        }
+        CommandResult::output(plain(out.lines().map(str::to_string)))
+    }
+}
+
+/// The characters a `tr` set stands for
+fn expand_set(set: &str) -> Vec<char> {
+    type Class = (&'static str, fn(&char) -> bool);
+    const CLASSES: &[Class] = &[
+        ("[:lower:]", char::is_ascii_lowercase),
+        ("[:upper:]", char::is_ascii_uppercase),
+        ("[:digit:]", char::is_ascii_digit),
+        ("[:alpha:]", char::is_ascii_alphabetic),
+        ("[:alnum:]", char::is_ascii_alphanumeric),
+        ("[:space:]", char::is_ascii_whitespace),
+        ("[:punct:]", char::is_ascii_punctuation),
+    ];
+
+    let mut out = Vec::new();
+    let mut rest = set;
+    while !rest.is_empty() {
+        if let Some((class, test)) = CLASSES.iter().find(|(class, _)| rest.starts_with(class)) {
+            out.extend((0..=127u8).map(char::from).filter(test));
+            rest = &rest[class.len()..];
+            continue;
+        }
+        let (c, len) = set_char(rest);
+        rest = &rest[len..];
+        // `a-z`: a range, unless the `-` is last
+        if let Some(after) = rest.strip_prefix('-')
+            && !after.is_empty()
+        {
+            let (end, end_len) = set_char(after);
+            out.extend(c..=end);
+            rest = &after[end_len..];
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// The first character of a set, with its length, decoding `\n`-style escapes
+fn set_char(text: &str) -> (char, usize) {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some('\\'), Some(escaped)) => {
+            let c = match escaped {
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                other => other,
+            };
+            (c, 1 + escaped.len_utf8())
+        }
+        (Some(c), _) => (c, c.len_utf8()),
+        (None, _) => ('\0', 0),
+    }
+}
+
+/// Collapse runs of any character in `set` to a single one
+fn squeeze_runs(text: &str, set: &[char]) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        if set.contains(&c) && out.ends_with(c) {
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::content::{ContentNode, NodeData};
+    use crate::shell::env::Environment;
+    use crate::shell::filesystem::HOME;
+    use crate::shell::history::History;
+
+    fn node(path: &str, data: NodeData) -> ContentNode {
+        ContentNode {
+            path: path.to_string(),
+            data,
+            mode: 0o644,
+            mtime: 0,
+        }
+    }
+
+    /// Run a tool in ~, which holds notes.txt without a final newline,
+    /// with `stdin` piped in if given. Returns stdout as text and the status.
+    fn run(command: &dyn Command, args: &[&str], stdin: Option<&str>) -> (Vec<String>, i32) {
+        let mut fs = VirtualFS::from_content(vec![
+            node("", NodeData::Dir),
+            node("notes.txt", NodeData::File(b"one two\nthree".as_slice().into())),
+        ])
+        .unwrap();
+        let (mut cwd, mut env, mut history) = (HOME.to_string(), Environment::new(), History::new());
+        let mut ctx = Context {
+            fs: &mut fs,
+            cwd: &mut cwd,
+            env: &mut env,
+            history: &mut history,
+            stdin: stdin.map(|text| text.lines().map(|line| Line::from(line.to_string())).collect()),
+            tty: false,
+        };
+        let result = command.run(args, &mut ctx);
+        let status = result.status();
+        let CommandResult::Output { stdout, .. } = result else {
+            panic!("{} did not print", command.name());
+        };
+        (stdout.iter().map(ui::line_to_string).collect(), status)
+    }
+
+    /// Stdout of a tool reading `stdin`
+    fn pipe(command: &dyn Command, args: &[&str], stdin: &str) -> Vec<String> {
+        let (stdout, status) = run(command, args, Some(stdin));
+        assert_eq!(status, 0, "{} {:?} failed", command.name(), args);
+        stdout
+    }
+
+    const FRUIT: &str = "Apple pie\nbanana\ncherry pie\nbanana\n";
+
+    #[test]
+    fn grep_selects_lines() {
+        assert_eq!(pipe(&Grep, &["pie"], FRUIT), ["Apple pie", "cherry pie"]);
+        assert_eq!(pipe(&Grep, &["-i", "apple"], FRUIT), ["Apple pie"]);
+        assert_eq!(pipe(&Grep, &["-v", "pie"], FRUIT), ["banana", "banana"]);
+        assert_eq!(pipe(&Grep, &["-n", "cherry"], FRUIT), ["3:cherry pie"]);
+        assert_eq!(pipe(&Grep, &["-c", "banana"], FRUIT), ["2"]);
+    }
+
+    #[test]
+    fn grep_status() {
+        assert_eq!(run(&Grep, &["kiwi"], Some(FRUIT)), (vec![], 1));
+        // Counting nothing still prints the count, and still fails
+        assert_eq!(run(&Grep, &["-c", "kiwi"], Some(FRUIT)), (vec!["0".to_string()], 1));
+        assert_eq!(run(&Grep, &["x", "missing.txt"], None).1, 2);
+        assert_eq!(run(&Grep, &[], Some(FRUIT)).1, 2);
+    }
+
+    #[test]
+    fn head_and_tail() {
+        assert_eq!(pipe(&Head, &["-n", "2"], FRUIT), ["Apple pie", "banana"]);
+        assert_eq!(pipe(&Head, &["-1"], FRUIT), ["Apple pie"]);
+        assert_eq!(pipe(&Tail, &["-n", "1"], FRUIT), ["banana"]);
+        assert_eq!(pipe(&Tail, &["-n", "+3"], FRUIT), ["cherry pie", "banana"]);
+        assert_eq!(pipe(&Head, &[], "a\nb"), ["a", "b"]);
+    }
+
+    #[test]
+    fn wc_counts() {
+        assert_eq!(pipe(&Wc, &["-l"], FRUIT), ["4"]);
+        assert_eq!(pipe(&Wc, &["-w"], FRUIT), ["6"]);
+        assert_eq!(pipe(&Wc, &["-c"], "ab\n"), ["3"]);
+    }
+
+    #[test]
+    fn wc_counts_newlines_not_lines() {
+        assert_eq!(run(&Wc, &["-l", "notes.txt"], None), (vec!["1 notes.txt".to_string()], 0));
+        assert_eq!(run(&Wc, &["-w", "notes.txt"], None).0, ["3 notes.txt"]);
+        assert_eq!(run(&Wc, &["-c", "notes.txt"], None).0, ["13 notes.txt"]);
+    }
+
+    #[test]
+    fn sort_orders_lines() {
+        assert_eq!(pipe(&Sort, &[], "b\nc\na\n"), ["a", "b", "c"]);
+        assert_eq!(pipe(&Sort, &["-r"], "b\nc\na\n"), ["c", "b", "a"]);
+        assert_eq!(pipe(&Sort, &["-n"], "10\n9\n100\n"), ["9", "10", "100"]);
+        assert_eq!(pipe(&Sort, &["-u"], "b\na\nb\n"), ["a", "b"]);
+    }
+
+    #[test]
+    fn uniq_merges_adjacent_lines() {
+        assert_eq!(pipe(&Uniq, &[], "a\na\nb\na\n"), ["a", "b", "a"]);
+        assert_eq!(pipe(&Uniq, &["-c"], "a\na\nb\n"), ["      2 a", "      1 b"]);
+    }
+
+    #[test]
+    fn cut_fields_and_characters() {
+        assert_eq!(pipe(&Cut, &["-d", ":", "-f", "1,3"], "a:b:c\nd:e:f\n"), ["a:c", "d:f"]);
+        assert_eq!(pipe(&Cut, &["-d", ",", "-f", "2-"], "a,b,c\n"), ["b,c"]);
+        assert_eq!(pipe(&Cut, &["-c", "1-3"], "abcdef\n"), ["abc"]);
+        // Lines without the delimiter pass through whole
+        assert_eq!(pipe(&Cut, &["-d", ":", "-f", "2"], "plain\n"), ["plain"]);
+    }
+
+    #[test]
+    fn tr_translates_deletes_and_squeezes() {
+        assert_eq!(pipe(&Tr, &["a-z", "A-Z"], "hello\n"), ["HELLO"]);
+        assert_eq!(pipe(&Tr, &["-d", "l"], "hello\n"), ["heo"]);
+        assert_eq!(pipe(&Tr, &["-s", " "], "a   b\n"), ["a b"]);
+        assert_eq!(pipe(&Tr, &["[:lower:]", "x"], "aB\n"), ["xB"]);
+    }
+}
@@ -0,0 +1,388 @@
+//! Variables, aliases and shell options
+
+use ratatui::text::Line;
+
+use super::exec::run_argv;
+use super::{error, ArgKind, Command, CommandResult, Context};
+use crate::shell::env::{is_valid_name, OPTIONS};
+use crate::ui;
+
+pub struct Export;
+
+impl Command for Export {
+    fn name(&self) -> &'static str {
+        "export"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "export [-p] [name[=value]...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Set and export variables"
+    }
+
+    fn help(&self) -> &'static str {
+        "Mark each name for export, setting it first when a value is given. \
+         Exported variables are the ones env shows and commands run with. \
+         With no names, list the exported variables.\n\n\
+         $PS1 sets the prompt; these escapes are replaced each time it is \
+         shown:\n\n\
+         \\u  user name        \\w  working directory\n\
+         \\h  host name        \\W  its last part\n\
+         \\$  a dollar sign    \\\\  a backslash"
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-p", "list the exported variables")]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("export EDITOR=nano", "set and export EDITOR"),
+            ("export PS1='\\w\\$ '", "a prompt with just the directory"),
+        ]
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        if matches!(args, [] | ["-p"]) {
+            return CommandResult::output(
+                ctx.env
+                    .exported()
+                    .map(|(name, value)| match value {
+                        Some(value) => Line::from(format!("export {}={}", name, quoted(value))),
+                        None => Line::from(format!("export {}", name)),
+                    })
+                    .collect(),
+            );
+        }
+
+        let mut errors = Vec::new();
+        for arg in args {
+            let (name, value) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (*arg, None),
+            };
+            if !is_valid_name(name) {
+                errors.push(not_an_identifier("export", arg));
+                continue;
+            }
+            if let Some(value) = value {
+                ctx.env.set(name, value);
+            }
+            ctx.env.export(name);
+        }
+        CommandResult::new(vec![], errors)
+    }
+}
+
+pub struct Unset;
+
+impl Command for Unset {
+    fn name(&self) -> &'static str {
+        "unset"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "unset [-f] <name...>"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Remove variables or functions"
+    }
+
+    fn help(&self) -> &'static str {
+        "Remove each variable, exported or not, or the function of that \
+         name if there is no such variable. Unsetting PS1 leaves an empty \
+         prompt."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-f", "remove functions only")]
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let (functions, names) = match args {
+            ["-f", names @ ..] => (true, names),
+            names => (false, names),
+        };
+        let mut errors = Vec::new();
+        for name in names {
+            if !is_valid_name(name) {
+                errors.push(not_an_identifier("unset", name));
+            } else if functions || ctx.env.get(name).is_none() {
+                ctx.env.unset_function(name);
+            } else {
+                ctx.env.unset(name);
+            }
+        }
+        CommandResult::new(vec![], errors)
+    }
+}
+
+pub struct Env;
+
+impl Command for Env {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "env [name=value...] [command [arg...]]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Print the environment, or run a command in a changed one"
+    }
+
+    fn help(&self) -> &'static str {
+        "With no command, print the exported variables. The assignments \
+         before the command are exported for it alone, like typing \
+         `name=value command`."
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("env | sort", "the exported variables"),
+            ("env COLUMNS=40 ls", "lay out the listing for a narrower screen"),
+        ]
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Commands
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let split = args
+            .iter()
+            .position(|arg| !arg.split_once('=').is_some_and(|(name, _)| is_valid_name(name)))
+            .unwrap_or(args.len());
+        let (assignments, command) = args.split_at(split);
+
+        let saved = ctx
+            .env
+            .set_temporary(assignments.iter().filter_map(|arg| arg.split_once('=')));
+        let result = if command.is_empty() {
+            CommandResult::output(
+                ctx.env
+                    .exported()
+                    .filter_map(|(name, value)| Some(Line::from(format!("{}={}", name, value?))))
+                    .collect(),
+            )
+        } else {
+            run_argv(command, ctx)
+        };
+        ctx.env.restore_temporary(saved);
+        result
+    }
+}
+
+pub struct Set;
+
+impl Command for Set {
+    fn name(&self) -> &'static str {
+        "set"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "set [-o|+o [option]]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Show variables or set shell options"
+    }
+
+    fn help(&self) -> &'static str {
+        "With no arguments, print every variable, exported or not. `set -o` lists the shell \
+         options; `set -o name` turns one on and `set +o name` turns it off.\n\n\
+         emacs  emacs-style line editing (the default)\n\
+         vi     vi-style line editing; Esc enters command mode"
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-o [option]", "turn an option on, or list them all"),
+            ("+o [option]", "turn an option off, or list them all"),
+        ]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[("set -o vi", "edit the command line with vi keys")]
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        match args {
+            [] => CommandResult::output(
+                ctx.env
+                    .vars()
+                    .map(|(name, value)| Line::from(format!("{}={}", name, quoted(value))))
+                    .collect(),
+            ),
+            ["-o"] | ["+o"] => CommandResult::output(
+                OPTIONS
+                    .iter()
+                    .map(|name| {
+                        let state = if ctx.env.option(name) { "on" } else { "off" };
+                        Line::from(format!("{:<15} {}", name, state))
+                    })
+                    .collect(),
+            ),
+            [flag @ ("-o" | "+o"), names @ ..] => {
+                for name in names {
+                    if let Err(e) = ctx.env.set_option(name, *flag == "-o") {
+                        return error(&format!("set: {}", e));
+                    }
+                }
+                CommandResult::output(vec![])
+            }
+            _ => error("set: usage: set [-o|+o [option]]"),
+        }
+    }
+}
+
+pub struct Alias;
+
+impl Command for Alias {
+    fn name(&self) -> &'static str {
+        "alias"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "alias [name[=value]...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Define or list aliases"
+    }
+
+    fn help(&self) -> &'static str {
+        "With no arguments, list the aliases. `alias name=value` makes a \
+         command starting with name run value instead, keeping the rest of \
+         the line, so after `alias ll='ls -la'` typing `ll blog` runs \
+         `ls -la blog`. With just a name, show that alias.\n\n\
+         Aliases are expanded as a line is read, so one defined on a line \
+         works from the next line on. The defaults are set in ~/.profile.\n\n\
+         For anything longer, define a function:\n\n\
+         blog() { cd ~/blog; ls -t; }"
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("alias", "list the aliases"),
+            ("alias posts='ls ~/blog'", "make posts list the blog"),
+        ]
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        if args.is_empty() {
+            return CommandResult::output(
+                ctx.env
+                    .aliases()
+                    .map(|(name, value)| Line::from(format!("alias {}={}", name, quoted(value))))
+                    .collect(),
+            );
+        }
+
+        let mut lines = Vec::new();
+        let mut errors = Vec::new();
+        for arg in args {
+            match arg.split_once('=') {
+                Some((name, value)) if is_alias_name(name) => ctx.env.set_alias(name, value),
+                Some((name, _)) => {
+                    errors.push(ui::error_text(&format!("alias: `{}': invalid alias name", name)));
+                }
+                None => match ctx.env.alias(arg) {
+                    Some(value) => lines.push(Line::from(format!("alias {}={}", arg, quoted(value)))),
+                    None => errors.push(ui::error_text(&format!("alias: {}: not found", arg))),
+                },
+            }
+        }
+        CommandResult::new(lines, errors)
+    }
+}
+
+/// Alias names can be anything that reads as a single plain word, like `..`
+fn is_alias_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name
+            .chars()
+            .any(|c| c.is_whitespace() || "/$`=\\'\"|&;()<>#".contains(c))
+}
+
+pub struct Unalias;
+
+impl Command for Unalias {
+    fn name(&self) -> &'static str {
+        "unalias"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "unalias [-a] <name...>"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Remove aliases"
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[("-a", "remove every alias")]
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Nothing
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        if args == ["-a"] {
+            let names: Vec<String> = ctx.env.aliases().map(|(name, _)| name.to_string()).collect();
+            for name in names {
+                ctx.env.unalias(&name);
+            }
+            return CommandResult::output(vec![]);
+        }
+        if args.is_empty() {
+            return error("unalias: usage: unalias [-a] name [name ...]");
+        }
+
+        let errors: Vec<_> = args
+            .iter()
+            .filter(|name| !ctx.env.unalias(name))
+            .map(|name| ui::error_text(&format!("unalias: {}: not found", name)))
+            .collect();
+        CommandResult::new(vec![], errors)
+    }
+}
+
+/// A value as it would have to be typed: single-quoted unless it is only
+/// characters the shell leaves alone
+fn quoted(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:@%+,=~".contains(c));
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+fn not_an_identifier(command: &str, arg: &str) -> Line<'static> {
+    ui::error_text(&format!("{}: `{}': not a valid identifier", command, arg))
+}
@@ -0,0 +1,373 @@
+//! `tree` and `find` - walk the filesystem recursively
+
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+
+use super::ls::ls_name;
+use super::{error, parse_options, ArgKind, Command, CommandResult, Context};
+use crate::shell::filesystem::child_path;
+use crate::shell::glob;
+use crate::shell::{FSEntry, VirtualFS};
+use crate::theme::Theme;
+use crate::ui;
+
+pub struct Tree;
+
+impl Command for Tree {
+    fn name(&self) -> &'static str {
+        "tree"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "tree [-a] [-d] [-L depth] [dir...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Show a directory tree"
+    }
+
+    fn help(&self) -> &'static str {
+        "Draw the directories (the current one by default) and everything \
+         below them as a tree, coloured like ls, then count the directories \
+         and files. Symlinks are shown but not followed."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-a", "include hidden files"),
+            ("-d", "directories only"),
+            ("-L depth", "descend at most depth levels"),
+        ]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[("tree -L 1 ~", "the home directory without its subdirectories' contents")]
+    }
+
+    fn completion(&self) -> ArgKind {
+        ArgKind::Dirs
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let (options, operands) = match parse_options("tree", args, "L") {
+            Ok(parsed) => parsed,
+            Err(e) => return e,
+        };
+        let mut opts = TreeOptions {
+            all: false,
+            dirs_only: false,
+            max_depth: usize::MAX,
+        };
+        for (flag, value) in options {
+            match flag {
+                'a' => opts.all = true,
+                'd' => opts.dirs_only = true,
+                'L' => match value.parse::<usize>() {
+                    Ok(depth) if depth > 0 => opts.max_depth = depth,
+                    _ => return error("tree: Invalid level, must be greater than 0."),
+                },
+                other => return error(&format!("tree: invalid option -- '{}'", other)),
+            }
+        }
+        let roots = if operands.is_empty() { vec!["."] } else { operands };
+
+        let mut lines = Vec::new();
+        let mut errors = Vec::new();
+        let mut counts = (0, 0);
+        for root in roots {
+            let path = ctx.fs.resolve_path(root, ctx.cwd);
+            match ctx.fs.stat(&path) {
+                Ok(FSEntry::Directory { .. }) => {
+                    let path = ctx.fs.follow(&path).unwrap_or(path);
+                    lines.push(Line::from(Span::styled(
+                        root.to_string(),
+                        Style::default().fg(Theme::PRIMARY),
+                    )));
+                    draw(ctx.fs, &path, "", 1, &opts, &mut lines, &mut counts);
+                }
+                Ok(_) => errors.push(ui::error_text(&format!("tree: {}: Not a directory", root))),
+                Err(reason) => errors.push(ui::error_text(&format!("tree: {}: {}", root, reason))),
+            }
+        }
+        if lines.is_empty() {
+            return CommandResult::failed(errors);
+        }
+
+        let (dirs, files) = counts;
+        let plural = |n: usize, one: &str, many: &str| format!("{} {}", n, if n == 1 { one } else { many });
+        let summary = if opts.dirs_only {
+            plural(dirs, "directory", "directories")
+        } else {
+            format!("{}, {}", plural(dirs, "directory", "directories"), plural(files, "file", "files"))
+        };
+        lines.extend([Line::from(""), Line::from(summary)]);
+        CommandResult::new(lines, errors)
+    }
+}
+
+struct TreeOptions {
+    all: bool,
+    dirs_only: bool,
+    max_depth: usize,
+}
+
+/// Draw the entries of `path` below their parent's line, counting
+/// directories and files as they go by
+fn draw(
+    fs: &VirtualFS,
+    path: &str,
+    indent: &str,
+    depth: usize,
+    opts: &TreeOptions,
+    lines: &mut Vec<Line<'static>>,
+    counts: &mut (usize, usize),
+) {
+    let mut entries: Vec<_> = fs
+        .list_dir(path)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(name, entry)| {
+            (opts.all || !name.starts_with('.'))
+                && (!opts.dirs_only || matches!(entry, FSEntry::Directory { .. }))
+        })
+        .collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let muted = Style::default().fg(Theme::MUTED);
+    let last = entries.len().saturating_sub(1);
+    for (i, (name, entry)) in entries.into_iter().enumerate() {
+        let (branch, continuation) = if i == last { ("└── ", "    ") } else { ("├── ", "│   ") };
+        let mut spans = vec![
+            Span::styled(format!("{}{}", indent, branch), muted),
+            ls_name(&name, entry),
+        ];
+        if let FSEntry::Symlink { target, .. } = entry {
+            spans.push(Span::raw(format!(" -> {}", target)));
+        }
+        lines.push(Line::from(spans));
+
+        if let FSEntry::Directory { .. } = entry {
+            counts.0 += 1;
+            if depth < opts.max_depth {
+                let indent = format!("{}{}", indent, continuation);
+                draw(fs, &child_path(path, &name), &indent, depth + 1, opts, lines, counts);
+            }
+        } else {
+            counts.1 += 1;
+        }
+    }
+}
+
+pub struct Find;
+
+impl Command for Find {
+    fn name(&self) -> &'static str {
+        "find"
+    }
+
+    fn synopsis(&self) -> &'static str {
+        "find [path...] [test...]"
+    }
+
+    fn summary(&self) -> &'static str {
+        "Search for files by name, type or size"
+    }
+
+    fn help(&self) -> &'static str {
+        "Walk each path (the current directory by default) and print every \
+         entry that passes all the tests. Symlinks are not followed."
+    }
+
+    fn options(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("-name pattern", "name matches a wildcard pattern like '*.md'"),
+            ("-iname pattern", "the same, ignoring case"),
+            ("-type t", "f file (executables too), d directory, x executable, l symlink"),
+            ("-maxdepth n", "descend at most n levels below the paths"),
+            (
+                "-size [+-]n",
+                "more than, less than or exactly n units, rounding up: c bytes, \
+                 k kilobytes, M megabytes, or 512-byte blocks by default",
+            ),
+        ]
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("find . -name '*.md'", "every markdown file below here"),
+            ("find ~ -type x", "the executables"),
+            ("find / -size +1k -type f", "files bigger than a kilobyte"),
+        ]
+    }
+
+    fn run(&self, args: &[&str], ctx: &mut Context) -> CommandResult {
+        let split = args
+            .iter()
+            .position(|arg| arg.starts_with('-'))
+            .unwrap_or(args.len());
+        let (paths, expression) = args.split_at(split);
+
+        let mut tests = Vec::new();
+        let mut max_depth = usize::MAX;
+        let mut expression = expression.iter();
+        while let Some(&predicate) = expression.next() {
+            let Some(&value) = expression.next() else {
+                return if is_predicate(predicate) {
+                    error(&format!("find: missing argument to `{}'", predicate))
+                } else {
+                    error(&format!("find: unknown predicate `{}'", predicate))
+                };
+            };
+            let test = match predicate {
+                "-name" => Test::Name(value.to_string()),
+                "-iname" => Test::NameIgnoringCase(value.to_lowercase()),
+                "-type" => match value {
+                    "f" | "d" | "x" | "l" => Test::Type(value.chars().next().unwrap_or('f')),
+                    _ => return error(&format!("find: Unknown argument to -type: {}", value)),
+                },
+                "-maxdepth" => match value.parse() {
+                    Ok(depth) => {
+                        max_depth = depth;
+                        continue;
+                    }
+                    Err(_) => {
+                        return error(&format!(
+                            "find: Expected a positive decimal integer argument to -maxdepth, but got `{}'",
+                            value
+                        ));
+                    }
+                },
+                "-size" => match parse_size(value) {
+                    Some(test) => test,
+                    None => return error(&format!("find: invalid argument `{}' to `-size'", value)),
+                },
+                other => return error(&format!("find: unknown predicate `{}'", other)),
+            };
+            tests.push(test);
+        }
+
+        let paths = if paths.is_empty() { &["."][..] } else { paths };
+        let mut found = Vec::new();
+        let mut errors = Vec::new();
+        for start in paths {
+            let path = ctx.fs.resolve_path(start, ctx.cwd);
+            match ctx.fs.get(&path) {
+                Some(entry) => {
+                    let name = path.rsplit('/').next().unwrap_or(&path);
+                    let name = if name.is_empty() { "/" } else { name };
+                    let walk = Walk {
+                        fs: ctx.fs,
+                        tests: &tests,
+                        max_depth,
+                    };
+                    walk.visit(start, &path, name, entry, 0, &mut found);
+                }
+                None => errors.push(ui::error_text(&format!(
+                    "find: '{}': No such file or directory",
+                    start
+                ))),
+            }
+        }
+
+        CommandResult::new(found.into_iter().map(Line::from).collect(), errors)
+    }
+}
+
+fn is_predicate(arg: &str) -> bool {
+    matches!(arg, "-name" | "-iname" | "-type" | "-maxdepth" | "-size")
+}
+
+/// One `find` test; an entry is printed when it passes them all
+enum Test {
+    Name(String),
+    /// `-iname`: the pattern is already lowercase
+    NameIgnoringCase(String),
+    Type(char),
+    /// Size in `unit`-byte units, rounded up, compared with `count`
+    Size {
+        ordering: std::cmp::Ordering,
+        count: usize,
+        unit: usize,
+    },
+}
+
+impl Test {
+    fn passes(&self, name: &str, entry: &FSEntry) -> bool {
+        match self {
+            Test::Name(pattern) => glob::matches_name(pattern, name),
+            Test::NameIgnoringCase(pattern) => glob::matches_name(pattern, &name.to_lowercase()),
+            Test::Type(kind) => matches!(
+                (kind, entry),
+                ('f', FSEntry::File { .. } | FSEntry::Binary { .. } | FSEntry::Executable { .. })
+                    | ('d', FSEntry::Directory { .. })
+                    | ('x', FSEntry::Executable { .. })
+                    | ('l', FSEntry::Symlink { .. })
+            ),
+            Test::Size { ordering, count, unit } => {
+                entry.meta().size.div_ceil(*unit).cmp(count) == *ordering
+            }
+        }
+    }
+}
+
+/// `+N`, `-N` or `N`, with an optional unit suffix
+fn parse_size(value: &str) -> Option<Test> {
+    let (ordering, rest) = match value.as_bytes().first()? {
+        b'+' => (std::cmp::Ordering::Greater, &value[1..]),
+        b'-' => (std::cmp::Ordering::Less, &value[1..]),
+        _ => (std::cmp::Ordering::Equal, value),
+    };
+    let (digits, unit) = match rest.char_indices().last()? {
+        (i, c) if c.is_ascii_alphabetic() => (&rest[..i], c),
+        _ => (rest, 'b'),
+    };
+    let unit = match unit {
+        'c' => 1,
+        'w' => 2,
+        'b' => 512,
+        'k' => 1024,
+        'M' => 1024 * 1024,
+        'G' => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    Some(Test::Size {
+        ordering,
+        count: digits.parse().ok()?,
+        unit,
+    })
+}
+
+/// One `find` walk over the filesystem
+struct Walk<'a> {
+    fs: &'a VirtualFS,
+    tests: &'a [Test],
+    max_depth: usize,
+}
+
+impl Walk<'_> {
+    /// Print `entry` if it passes, then descend into it if it is a directory
+    fn visit(
+        &self,
+        display: &str,
+        path: &str,
+        name: &str,
+        entry: &FSEntry,
+        depth: usize,
+        found: &mut Vec<String>,
+    ) {
+        if self.tests.iter().all(|test| test.passes(name, entry)) {
+            found.push(display.to_string());
+        }
+        if depth >= self.max_depth {
+            return;
+        }
+        if let FSEntry::Directory { .. } = entry {
+            for (child, child_entry) in self.fs.list_dir(path).unwrap_or_default() {
+                let shown = format!("{}/{}", display.trim_end_matches('/'), child);
+                self.visit(&shown, &child_path(path, &child), &child, child_entry, depth + 1, found);
+            }
+        }
+    }
+}
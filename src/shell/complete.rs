@@ -0,0 +1,119 @@
+//! Tab completion - command names in command position, paths everywhere else
+
+use super::commands::{self, ArgKind};
+use super::filesystem::{child_path, HOME};
+use super::{FSEntry, VirtualFS};
+
+/// Completions for the word ending at the cursor
+pub struct Completion {
+    /// Byte offset where the word being completed starts
+    pub start: usize,
+    /// Full replacements for the word, sorted
+    pub candidates: Vec<String>,
+}
+
+/// Characters that end a word for completion purposes
+fn is_break(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '|' | '&' | ';' | '>')
+}
+
+/// Complete the word ending at the end of `line` (the text before the cursor)
+pub fn complete(line: &str, fs: &VirtualFS, cwd: &str) -> Completion {
+    let start = line
+        .char_indices()
+        .rev()
+        .find(|(_, c)| is_break(*c))
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    let word = &line[start..];
+
+    // The command this word belongs to, if the word is not the command itself
+    let segment_start = line[..start].rfind(['|', '&', ';']).map_or(0, |i| i + 1);
+    let command = line[segment_start..start].split_whitespace().next();
+    let after_redirect = line[..start].trim_end().ends_with('>');
+
+    let kind = match command {
+        _ if after_redirect => ArgKind::Paths,
+        None if word.contains('/') => ArgKind::Paths,
+        None => ArgKind::Commands,
+        Some(name) => commands::find(name).map_or(ArgKind::Paths, |command| command.completion()),
+    };
+    let mut candidates = match kind {
+        ArgKind::Paths => complete_path(word, fs, cwd, false),
+        ArgKind::Dirs => complete_path(word, fs, cwd, true),
+        ArgKind::Commands => complete_command(word, fs),
+        ArgKind::Nothing => Vec::new(),
+    };
+    candidates.sort();
+    candidates.dedup();
+    Completion { start, candidates }
+}
+
+/// Builtin names and aliases, and the executables in the home directory
+fn complete_command(word: &str, fs: &VirtualFS) -> Vec<String> {
+    let builtins = commands::all()
+        .iter()
+        .flat_map(|command| std::iter::once(command.name()).chain(command.aliases().iter().copied()))
+        .map(str::to_string);
+    let executables = fs
+        .list_dir(HOME)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, entry)| matches!(entry, FSEntry::Executable { .. }))
+        .map(|(name, _)| name);
+    builtins
+        .chain(executables)
+        .filter(|name| name.starts_with(word))
+        .collect()
+}
+
+/// Entries of the directory named by everything up to the word's last `/`
+fn complete_path(word: &str, fs: &VirtualFS, cwd: &str, dirs_only: bool) -> Vec<String> {
+    let (dir, prefix) = match word.rfind('/') {
+        Some(i) => (&word[..=i], &word[i + 1..]),
+        None => ("", word),
+    };
+    let dir_path = if dir.is_empty() {
+        cwd.to_string()
+    } else {
+        fs.resolve_path(dir, cwd)
+    };
+    let Ok(dir_path) = fs.follow(&dir_path) else {
+        return Vec::new();
+    };
+
+    let mut candidates = Vec::new();
+    for (name, entry) in fs.list_dir(&dir_path).unwrap_or_default() {
+        // Hidden entries only when asked for with a leading dot
+        if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+            continue;
+        }
+        let is_dir = match entry {
+            FSEntry::Symlink { .. } => fs.stat(&child_path(&dir_path, &name)).is_ok_and(FSEntry::is_dir),
+            entry => entry.is_dir(),
+        };
+        if dirs_only && !is_dir {
+            continue;
+        }
+        let suffix = if is_dir { "/" } else { "" };
+        candidates.push(format!("{}{}{}", dir, name, suffix));
+    }
+    candidates
+}
+
+/// Longest prefix shared by every candidate
+pub fn common_prefix(candidates: &[String]) -> String {
+    let Some(first) = candidates.first() else {
+        return String::new();
+    };
+    let mut len = first.len();
+    for candidate in &candidates[1..] {
+        len = first
+            .char_indices()
+            .zip(candidate.chars())
+            .take_while(|((i, a), b)| *i < len && a == b)
+            .map(|((i, a), _)| i + a.len_utf8())
+            .last()
+            .unwrap_or(0);
+    }
+    first[..len].to_string()
+}
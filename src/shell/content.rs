@@ -0,0 +1,114 @@
+//! Content sources for the virtual filesystem: a directory on disk, or a
+//! tree baked into the binary at build time
+
+use std::borrow::Cow;
+use std::io;
+use std::path::Path;
+
+use super::content_walk::{self, WalkedKind};
+
+/// Manifest at the content root declaring which paths are executables
+pub const MANIFEST_NAME: &str = ".manifest";
+
+/// What a content node holds
+pub enum NodeData {
+    Dir,
+    File(Cow<'static, [u8]>),
+    /// Symbolic link, with its target as written
+    Symlink(String),
+}
+
+/// One file or directory from a content tree
+pub struct ContentNode {
+    /// Path relative to the content root, `/`-separated; the root itself is ""
+    pub path: String,
+    pub data: NodeData,
+    /// Unix permission bits
+    pub mode: u32,
+    /// Modification time in seconds since the Unix epoch
+    pub mtime: u64,
+}
+
+/// Read every node below `root`, parents before children
+pub fn read_dir_tree(root: &Path) -> io::Result<Vec<ContentNode>> {
+    content_walk::walk(root)?
+        .into_iter()
+        .map(|entry| {
+            let data = match entry.kind {
+                WalkedKind::Dir => NodeData::Dir,
+                WalkedKind::File(path) => NodeData::File(Cow::Owned(std::fs::read(path)?)),
+                WalkedKind::Symlink(target) => NodeData::Symlink(target),
+            };
+            Ok(ContentNode {
+                path: entry.path,
+                data,
+                mode: entry.mode,
+                mtime: entry.mtime,
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "embed-content")]
+enum EmbeddedData {
+    Dir,
+    File(&'static [u8]),
+    Symlink(&'static str),
+}
+
+#[cfg(feature = "embed-content")]
+struct EmbeddedNode {
+    path: &'static str,
+    data: EmbeddedData,
+    mode: u32,
+    mtime: u64,
+}
+
+/// Generated by build.rs from the content directory
+#[cfg(feature = "embed-content")]
+static EMBEDDED: &[EmbeddedNode] = include!(concat!(env!("OUT_DIR"), "/content.rs"));
+
+/// The content tree baked in at build time
+#[cfg(feature = "embed-content")]
+pub fn embedded() -> Vec<ContentNode> {
+    EMBEDDED
+        .iter()
+        .map(|node| ContentNode {
+            path: node.path.to_string(),
+            data: match node.data {
+                EmbeddedData::Dir => NodeData::Dir,
+                EmbeddedData::File(bytes) => NodeData::File(Cow::Borrowed(bytes)),
+                EmbeddedData::Symlink(target) => NodeData::Symlink(target.to_string()),
+            },
+            mode: node.mode,
+            mtime: node.mtime,
+        })
+        .collect()
+}
+
+/// `ls -l` style permission string, e.g. `drwxr-xr-x`. `kind` is the
+/// file type character (`-`, `d` or `l`).
+pub fn format_permissions(kind: char, mode: u32) -> String {
+    let mut out = String::from(kind);
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 0o7;
+        out.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        out.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        out.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+    }
+    out
+}
+
+/// Parse manifest lines of the form `<path>  <description>`.
+/// Blank lines and `#` comments are ignored.
+pub fn parse_manifest(manifest: &str) -> Vec<(&str, String)> {
+    manifest
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.split_once(char::is_whitespace) {
+            Some((path, description)) => (path, description.trim().to_string()),
+            None => (line, String::new()),
+        })
+        .collect()
+}
@@ -0,0 +1,105 @@
+//! Walking a content directory on disk. Shared by the runtime loader and
+//! build.rs, which includes this file with `#[path]`, so it may only use std.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// What a walked entry is
+pub enum WalkedKind {
+    Dir,
+    /// Path of the file on disk; reading it is left to the caller
+    File(PathBuf),
+    /// Target of a relative symlink, `/`-separated
+    Symlink(String),
+}
+
+/// One entry found by [`walk`]
+pub struct WalkedEntry {
+    /// Path relative to the root, `/`-separated; the root itself is ""
+    pub path: String,
+    pub kind: WalkedKind,
+    /// Unix permission bits
+    pub mode: u32,
+    /// Modification time in seconds since the Unix epoch
+    pub mtime: u64,
+}
+
+/// Every entry below `root`, parents before children and siblings sorted
+/// by name. Relative symlinks stay links so they keep pointing inside the
+/// tree; other links are followed so the tree can pull in files from
+/// elsewhere.
+pub fn walk(root: &Path) -> io::Result<Vec<WalkedEntry>> {
+    let mut entries = Vec::new();
+    walk_dir(root, "", &mut entries)?;
+    Ok(entries)
+}
+
+fn walk_dir(dir: &Path, rel: &str, out: &mut Vec<WalkedEntry>) -> io::Result<()> {
+    let metadata = fs::metadata(dir)?;
+    out.push(WalkedEntry {
+        path: rel.to_string(),
+        kind: WalkedKind::Dir,
+        mode: mode(&metadata),
+        mtime: mtime(&metadata),
+    });
+
+    let mut entries: Vec<_> = fs::read_dir(dir)?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let child_rel = if rel.is_empty() { name } else { format!("{}/{}", rel, name) };
+        let path = entry.path();
+
+        let link = fs::symlink_metadata(&path)?;
+        if link.file_type().is_symlink() {
+            let target = fs::read_link(&path)?;
+            if target.is_relative() {
+                out.push(WalkedEntry {
+                    path: child_rel,
+                    kind: WalkedKind::Symlink(target.to_string_lossy().replace('\\', "/")),
+                    mode: 0o777,
+                    mtime: mtime(&link),
+                });
+                continue;
+            }
+        }
+
+        let metadata = fs::metadata(&path)?;
+        if metadata.is_dir() {
+            walk_dir(&path, &child_rel, out)?;
+        } else {
+            out.push(WalkedEntry {
+                path: child_rel,
+                kind: WalkedKind::File(path),
+                mode: mode(&metadata),
+                mtime: mtime(&metadata),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn mtime(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(unix)]
+fn mode(metadata: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn mode(metadata: &fs::Metadata) -> u32 {
+    match (metadata.is_dir(), metadata.permissions().readonly()) {
+        (true, _) => 0o755,
+        (false, true) => 0o444,
+        (false, false) => 0o644,
+    }
+}
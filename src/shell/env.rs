@@ -0,0 +1,285 @@
+//! Per-session variables, aliases, functions and shell options
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::filesystem::HOME;
+use super::parser::CommandList;
+
+/// Profile sourced at login, in the home directory
+pub const PROFILE_NAME: &str = ".profile";
+
+/// Prompt used until `$PS1` is set to something else
+const DEFAULT_PS1: &str = "\\u@\\h:\\w\\$ ";
+
+/// How deeply sourced files and function calls may nest before it is
+/// taken for a loop
+const MAX_DEPTH: usize = 64;
+
+/// Options `set -o` knows about
+pub const OPTIONS: &[&str] = &["emacs", "vi"];
+
+/// Variables for one shell session. Exported ones are what `env` shows;
+/// the rest are shell variables, only seen by `set` and expansion.
+#[derive(Clone)]
+pub struct Environment {
+    vars: BTreeMap<String, String>,
+    /// Names marked with `export`, whether or not they have a value yet
+    exported: BTreeSet<String>,
+    /// `alias` definitions, expanded where a command starts
+    aliases: BTreeMap<String, String>,
+    /// Functions defined with `name() { ...; }`
+    functions: BTreeMap<String, CommandList>,
+    /// Options that are on, from [`OPTIONS`]
+    options: BTreeSet<&'static str>,
+    /// Sourced files and functions being run, innermost last
+    depth: usize,
+    /// Exit status of the last pipeline, `$?`
+    status: i32,
+    /// `$0`: the shell, or the script being run
+    arg0: String,
+    /// `$1`, `$2`, ... of the script or function being run
+    positional: Vec<String>,
+}
+
+/// A variable as it was before a command's `NAME=value` prefix
+pub struct SavedVar {
+    name: String,
+    value: Option<String>,
+    exported: bool,
+}
+
+impl Environment {
+    /// The login environment, before `~/.profile` is sourced
+    pub fn new() -> Self {
+        let mut env = Self {
+            vars: BTreeMap::new(),
+            exported: BTreeSet::new(),
+            aliases: BTreeMap::new(),
+            functions: BTreeMap::new(),
+            options: BTreeSet::from(["emacs"]),
+            depth: 0,
+            status: 0,
+            arg0: "rsh".to_string(),
+            positional: Vec::new(),
+        };
+        for (name, value) in [
+            ("HOME", HOME),
+            ("USER", "guest"),
+            ("LOGNAME", "guest"),
+            ("HOSTNAME", "uchindami"),
+            ("SHELL", "/bin/rsh"),
+            ("TERM", "xterm-256color"),
+        ] {
+            env.set(name, value);
+            env.export(name);
+        }
+        env.set("PS1", DEFAULT_PS1);
+        env
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str)
+    }
+
+    /// A variable or special parameter, as `$name` expands
+    pub fn param(&self, name: &str) -> Option<Cow<'_, str>> {
+        match name {
+            "?" => Some(self.status.to_string().into()),
+            "#" => Some(self.positional.len().to_string().into()),
+            "@" | "*" => Some(self.positional.join(" ").into()),
+            "0" => Some(self.arg0.as_str().into()),
+            _ => match name.parse::<usize>() {
+                Ok(n) => n
+                    .checked_sub(1)
+                    .and_then(|i| self.positional.get(i))
+                    .map(|arg| arg.as_str().into()),
+                Err(_) => self.get(name).map(Cow::from),
+            },
+        }
+    }
+
+    /// Set a variable, keeping it exported if it already was
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.vars.insert(name.to_string(), value.to_string());
+    }
+
+    /// Mark a variable for export
+    pub fn export(&mut self, name: &str) {
+        self.exported.insert(name.to_string());
+    }
+
+    /// Remove a variable and its export mark
+    pub fn unset(&mut self, name: &str) {
+        self.vars.remove(name);
+        self.exported.remove(name);
+    }
+
+    /// Set and export variables for the length of one command, returning
+    /// what they were so [`Environment::restore_temporary`] can put them back
+    pub fn set_temporary<'a>(
+        &mut self,
+        assignments: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Vec<SavedVar> {
+        let mut saved = Vec::new();
+        for (name, value) in assignments {
+            saved.push(SavedVar {
+                name: name.to_string(),
+                value: self.vars.get(name).cloned(),
+                exported: self.exported.contains(name),
+            });
+            self.set(name, value);
+            self.export(name);
+        }
+        saved
+    }
+
+    /// Put back variables saved by [`Environment::set_temporary`], leaving
+    /// everything else the command changed as it is
+    pub fn restore_temporary(&mut self, saved: Vec<SavedVar>) {
+        // Latest first, so a name assigned twice ends up as it started
+        for SavedVar { name, value, exported } in saved.into_iter().rev() {
+            match value {
+                Some(value) => self.vars.insert(name.clone(), value),
+                None => self.vars.remove(&name),
+            };
+            if exported {
+                self.exported.insert(name);
+            } else {
+                self.exported.remove(&name);
+            }
+        }
+    }
+
+    /// Every variable, sorted by name
+    pub fn vars(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.vars.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Exported variables, sorted by name. Names exported without a value
+    /// have `None`.
+    pub fn exported(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.exported.iter().map(|name| (name.as_str(), self.get(name)))
+    }
+
+    pub fn alias(&self, name: &str) -> Option<&str> {
+        self.aliases.get(name).map(String::as_str)
+    }
+
+    pub fn set_alias(&mut self, name: &str, value: &str) {
+        self.aliases.insert(name.to_string(), value.to_string());
+    }
+
+    /// Remove an alias, returning whether there was one
+    pub fn unalias(&mut self, name: &str) -> bool {
+        self.aliases.remove(name).is_some()
+    }
+
+    /// Every alias, sorted by name
+    pub fn aliases(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.aliases.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    pub fn function(&self, name: &str) -> Option<&CommandList> {
+        self.functions.get(name)
+    }
+
+    pub fn set_function(&mut self, name: &str, body: CommandList) {
+        self.functions.insert(name.to_string(), body);
+    }
+
+    /// Remove a function, returning whether there was one
+    pub fn unset_function(&mut self, name: &str) -> bool {
+        self.functions.remove(name).is_some()
+    }
+
+    pub fn status(&self) -> i32 {
+        self.status
+    }
+
+    pub fn set_status(&mut self, status: i32) {
+        self.status = status;
+    }
+
+    pub fn positional(&self) -> &[String] {
+        &self.positional
+    }
+
+    /// Replace `$0` (when given) and the positional parameters for a script
+    /// or function, returning the old ones to put back when it finishes
+    pub fn set_positional(&mut self, arg0: Option<&str>, args: Vec<String>) -> (String, Vec<String>) {
+        let old_arg0 = match arg0 {
+            Some(arg0) => std::mem::replace(&mut self.arg0, arg0.to_string()),
+            None => self.arg0.clone(),
+        };
+        (old_arg0, std::mem::replace(&mut self.positional, args))
+    }
+
+    /// Put back parameters saved by [`Environment::set_positional`]
+    pub fn restore_positional(&mut self, (arg0, args): (String, Vec<String>)) {
+        self.arg0 = arg0;
+        self.positional = args;
+    }
+
+    /// Drop the first `n` positional parameters, failing if there are fewer
+    pub fn shift(&mut self, n: usize) -> bool {
+        if n > self.positional.len() {
+            return false;
+        }
+        self.positional.drain(..n);
+        true
+    }
+
+    /// Start running a sourced file or function, unless too many already are
+    pub fn enter(&mut self) -> Result<(), String> {
+        if self.depth >= MAX_DEPTH {
+            return Err("maximum nesting depth exceeded".to_string());
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Finish running a sourced file or function
+    pub fn leave(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    pub fn option(&self, name: &str) -> bool {
+        self.options.contains(name)
+    }
+
+    /// Turn an option on or off. `emacs` and `vi` pick the editing mode, so
+    /// setting one clears the other and clearing one falls back to emacs.
+    pub fn set_option(&mut self, name: &str, on: bool) -> Result<(), String> {
+        let Some(&name) = OPTIONS.iter().find(|option| **option == name) else {
+            return Err(format!("{}: invalid option name", name));
+        };
+        match (name, on) {
+            ("emacs" | "vi", true) => {
+                self.options.remove("emacs");
+                self.options.remove("vi");
+                self.options.insert(name);
+            }
+            ("emacs" | "vi", false) => {
+                self.options.remove(name);
+                if !self.options.contains("vi") {
+                    self.options.insert("emacs");
+                }
+            }
+            (name, true) => {
+                self.options.insert(name);
+            }
+            (name, false) => {
+                self.options.remove(name);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `name` is a valid variable name
+pub fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,198 @@
+//! Word expansion - variables, field splitting and globbing
+
+use super::env::{is_valid_name, Environment};
+use super::glob;
+use super::lexer::{Word, WordPart};
+use super::VirtualFS;
+
+/// A word after variable expansion
+struct Field {
+    text: String,
+    /// `text` with quoted wildcard characters backslash-escaped
+    pattern: String,
+    /// Whether an unquoted wildcard appeared
+    glob: bool,
+}
+
+impl Field {
+    fn new() -> Self {
+        Self {
+            text: String::new(),
+            pattern: String::new(),
+            glob: false,
+        }
+    }
+
+    fn push_str(&mut self, s: &str, quoted: bool) {
+        self.text.push_str(s);
+        for c in s.chars() {
+            let special = matches!(c, '*' | '?' | '[' | ']' | '\\');
+            if special && quoted {
+                self.pattern.push('\\');
+            }
+            self.glob |= special && !quoted && c != '\\';
+            self.pattern.push(c);
+        }
+    }
+}
+
+/// Expand a word into zero or more fields.
+/// Unquoted variable values are split on whitespace, quoted ones never are.
+fn expand_fields(word: &Word, env: &Environment) -> Vec<Field> {
+    let mut fields = Vec::new();
+    let mut current = Field::new();
+    // Quoted text makes a field exist even when it is empty (`""`)
+    let mut has_field = false;
+
+    for part in &word.parts {
+        match part {
+            WordPart::Literal { text, quoted } => {
+                current.push_str(text, *quoted);
+                has_field |= *quoted || !text.is_empty();
+            }
+            // `"$@"` is each positional parameter as a field of its own
+            WordPart::Var { name, quoted: true } if name == "@" => {
+                for (i, arg) in env.positional().iter().enumerate() {
+                    if i > 0 {
+                        fields.push(std::mem::replace(&mut current, Field::new()));
+                    }
+                    current.push_str(arg, true);
+                    has_field = true;
+                }
+            }
+            WordPart::Var { name, quoted: true } => {
+                current.push_str(&env.param(name).unwrap_or_default(), true);
+                has_field = true;
+            }
+            WordPart::Var { name, quoted: false } => {
+                let value = env.param(name).unwrap_or_default();
+                if value.starts_with(char::is_whitespace) && has_field {
+                    fields.push(std::mem::replace(&mut current, Field::new()));
+                    has_field = false;
+                }
+                for (i, piece) in value.split_whitespace().enumerate() {
+                    if i > 0 {
+                        fields.push(std::mem::replace(&mut current, Field::new()));
+                    }
+                    current.push_str(piece, false);
+                    has_field = true;
+                }
+                if value.ends_with(char::is_whitespace) && has_field {
+                    fields.push(std::mem::replace(&mut current, Field::new()));
+                    has_field = false;
+                }
+            }
+        }
+    }
+
+    if has_field {
+        fields.push(current);
+    }
+    fields
+}
+
+/// Expand a word without globbing
+pub fn expand_word(word: &Word, env: &Environment) -> Vec<String> {
+    expand_fields(word, env).into_iter().map(|field| field.text).collect()
+}
+
+/// Split a `NAME=value` word into the name and its expanded value. The
+/// name must be unquoted, and the value is never field-split or globbed.
+pub fn expand_assignment(word: &Word, env: &Environment) -> Option<(String, String)> {
+    let Some(WordPart::Literal { text, quoted: false }) = word.parts.first() else {
+        return None;
+    };
+    let (name, rest) = text.split_once('=')?;
+    if !is_valid_name(name) {
+        return None;
+    }
+
+    let mut value = rest.to_string();
+    for part in &word.parts[1..] {
+        match part {
+            WordPart::Literal { text, .. } => value.push_str(text),
+            WordPart::Var { name, .. } => value.push_str(&env.param(name).unwrap_or_default()),
+        }
+    }
+    Some((name.to_string(), value))
+}
+
+/// Expand every word of a command line, in order. Wildcards that match
+/// nothing are left as typed, like bash without `nullglob`.
+pub fn expand_words(words: &[Word], env: &Environment, fs: &VirtualFS, cwd: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    for field in words.iter().flat_map(|word| expand_fields(word, env)) {
+        if field.glob {
+            let matches = glob::expand(&field.pattern, fs, cwd);
+            if !matches.is_empty() {
+                out.extend(matches);
+                continue;
+            }
+        }
+        out.push(field.text);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::content::{ContentNode, NodeData};
+    use super::super::lexer::{self, Token};
+    use super::*;
+
+    fn node(path: &str, data: NodeData) -> ContentNode {
+        ContentNode {
+            path: path.to_string(),
+            data,
+            mode: 0o644,
+            mtime: 0,
+        }
+    }
+
+    /// `input` expanded as a command line in ~ holding a.md, b.md and c.txt
+    fn expand(input: &str, env: &Environment) -> Vec<String> {
+        let file = |name| node(name, NodeData::File(b"".as_slice().into()));
+        let fs = VirtualFS::from_content(vec![node("", NodeData::Dir), file("a.md"), file("b.md"), file("c.txt")])
+            .unwrap();
+        let words: Vec<Word> = lexer::tokenize(input)
+            .unwrap()
+            .into_iter()
+            .filter_map(|token| match token {
+                Token::Word(word) => Some(word),
+                _ => None,
+            })
+            .collect();
+        expand_words(&words, env, &fs, "/home/guest")
+    }
+
+    #[test]
+    fn unquoted_patterns_glob() {
+        let env = Environment::new();
+        assert_eq!(expand("*.md", &env), ["a.md", "b.md"]);
+        assert_eq!(expand("?.txt x", &env), ["c.txt", "x"]);
+    }
+
+    #[test]
+    fn quoted_patterns_stay_literal() {
+        let env = Environment::new();
+        assert_eq!(expand("'*.md' \"?.txt\" \\*", &env), ["*.md", "?.txt", "*"]);
+        // Only the unquoted part of a word globs
+        assert_eq!(expand("'['a].md", &env), ["[a].md"]);
+        assert_eq!(expand("\"a\"*", &env), ["a.md"]);
+    }
+
+    #[test]
+    fn patterns_without_matches_are_kept() {
+        let env = Environment::new();
+        assert_eq!(expand("*.pdf", &env), ["*.pdf"]);
+        assert_eq!(expand("[xy]*", &env), ["[xy]*"]);
+    }
+
+    #[test]
+    fn variables_glob_unless_quoted() {
+        let mut env = Environment::new();
+        env.set("P", "*.md");
+        assert_eq!(expand("$P", &env), ["a.md", "b.md"]);
+        assert_eq!(expand("\"$P\"", &env), ["*.md"]);
+    }
+}
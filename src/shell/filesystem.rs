@@ -1,207 +1,627 @@
 //! Virtual filesystem for the shell
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::io;
+
+use super::content::{self, ContentNode, NodeData, MANIFEST_NAME};
+use super::time;
+
+/// The visitor's home directory, which `~` stands for
+pub const HOME: &str = "/home/guest";
+
+/// Owner of the portfolio content
+const CONTENT_OWNER: &str = "uchindami";
+/// The visitor, who owns everything created during a session
+const SESSION_OWNER: &str = "guest";
+/// Size reported for every directory
+const DIR_SIZE: usize = 512;
+/// Most symlinks followed while resolving one path, as in Linux
+const MAX_SYMLINK_HOPS: usize = 40;
+/// Error for symlink loops (`ELOOP`)
+const ELOOP: &str = "Too many levels of symbolic links";
+
+/// Ownership, permissions and timestamps of an entry
+#[derive(Clone)]
+pub struct Metadata {
+    /// Unix permission bits
+    pub mode: u32,
+    pub owner: &'static str,
+    pub group: &'static str,
+    /// Size in bytes
+    pub size: usize,
+    /// Modification time in seconds since the Unix epoch
+    pub mtime: u64,
+    /// Hard link count; for a directory, 2 plus its subdirectories
+    pub nlink: usize,
+}
+
+impl Metadata {
+    fn new(mode: u32, owner: &'static str, size: usize, mtime: u64) -> Self {
+        Self {
+            mode,
+            owner,
+            group: owner,
+            size,
+            mtime,
+            nlink: 1,
+        }
+    }
+
+    /// Metadata for a directory with no subdirectories yet
+    fn dir(mode: u32, owner: &'static str, mtime: u64) -> Self {
+        Self {
+            nlink: 2,
+            ..Self::new(mode, owner, DIR_SIZE, mtime)
+        }
+    }
+
+    /// Metadata for a directory above the content tree (`/`, `/home`)
+    fn system_dir(mtime: u64) -> Self {
+        Self::dir(0o755, "root", mtime)
+    }
+}
 
 /// Entry in the virtual filesystem
 #[derive(Clone)]
 pub enum FSEntry {
     File {
         content: String,
-        size: usize,
-        permissions: &'static str,
+        meta: Metadata,
+    },
+    /// File whose content is not text (a PDF, an image); it can be listed
+    /// and moved around, but not read
+    Binary {
+        meta: Metadata,
     },
     Directory {
         children: Vec<String>,
+        meta: Metadata,
     },
     Executable {
-        description: &'static str,
-        size: usize,
+        description: String,
+        meta: Metadata,
+    },
+    Symlink {
+        /// Target as written; relative targets start from the link's directory
+        target: String,
+        meta: Metadata,
     },
 }
 
-/// Virtual filesystem
+impl FSEntry {
+    pub fn meta(&self) -> &Metadata {
+        match self {
+            FSEntry::File { meta, .. }
+            | FSEntry::Binary { meta }
+            | FSEntry::Directory { meta, .. }
+            | FSEntry::Executable { meta, .. }
+            | FSEntry::Symlink { meta, .. } => meta,
+        }
+    }
+
+    fn meta_mut(&mut self) -> &mut Metadata {
+        match self {
+            FSEntry::File { meta, .. }
+            | FSEntry::Binary { meta }
+            | FSEntry::Directory { meta, .. }
+            | FSEntry::Executable { meta, .. }
+            | FSEntry::Symlink { meta, .. } => meta,
+        }
+    }
+
+    pub fn is_dir(&self) -> bool {
+        matches!(self, FSEntry::Directory { .. })
+    }
+
+    /// `ls -l` style mode string, e.g. `drwxr-xr-x`
+    pub fn permissions(&self) -> String {
+        let kind = match self {
+            FSEntry::Directory { .. } => 'd',
+            FSEntry::Symlink { .. } => 'l',
+            FSEntry::File { .. } | FSEntry::Binary { .. } | FSEntry::Executable { .. } => '-',
+        };
+        content::format_permissions(kind, self.meta().mode)
+    }
+}
+
+/// Most bytes of file content a session may write
+const MAX_OVERLAY_BYTES: usize = 1024 * 1024;
+/// Most files and directories a session may create or modify
+const MAX_OVERLAY_ENTRIES: usize = 512;
+
+/// Virtual filesystem: read-only baseline content with a per-session
+/// copy-on-write overlay on top
 pub struct VirtualFS {
-    entries: HashMap<String, FSEntry>,
+    /// Baseline content, never modified after loading
+    base: HashMap<String, FSEntry>,
+    /// Session changes; `None` marks a deleted baseline entry
+    overlay: HashMap<String, Option<FSEntry>>,
 }
 
 impl VirtualFS {
-    pub fn new() -> Self {
+    /// Build the filesystem from a content tree, whether read from disk
+    /// or embedded. The tree becomes the home directory: files and
+    /// directories map one to one, and `.manifest` at the root marks which
+    /// paths are executables.
+    pub fn from_content(nodes: Vec<ContentNode>) -> io::Result<Self> {
         let mut entries = HashMap::new();
+        let mut manifest = String::new();
+        let mut manifest_mtime = 0;
 
-        // Root directory
-        entries.insert(
-            "~".to_string(),
-            FSEntry::Directory {
-                children: vec![
-                    ".profile".to_string(),
-                    ".secret".to_string(),
-                    "README.md".to_string(),
-                    "about".to_string(),
-                    "dashboard".to_string(),
-                    "resume".to_string(),
-                    "contact".to_string(),
-                    "blog".to_string(),
-                    "downloads".to_string(),
-                ],
-            },
-        );
+        let root_mtime = nodes.first().map_or(0, |node| node.mtime);
+        for path in ["/", "/home"] {
+            let meta = Metadata::system_dir(root_mtime);
+            entries.insert(path.to_string(), FSEntry::Directory { children: Vec::new(), meta });
+        }
+        add_child(&mut entries, "/", "home")?;
 
-        // Files
-        entries.insert(
-            "~/.profile".to_string(),
-            FSEntry::File {
-                content: "# Uchindami's Shell Profile\nexport EDITOR=nvim\nexport LANG=en_US.UTF-8\n".to_string(),
-                size: 220,
-                permissions: "-rw-r--r--",
-            },
-        );
+        for node in nodes {
+            let path = child_path(HOME, &node.path);
 
-        entries.insert(
-            "~/.secret".to_string(),
-            FSEntry::File {
-                content: "You found a secret! Here's a cookie: 🍪\n\nP.S. Try running 'hack' or 'sudo rm -rf /' 😉".to_string(),
-                size: 180,
-                permissions: "-rw-------",
-            },
-        );
+            match node.data {
+                NodeData::Dir => {
+                    entries.insert(
+                        path.clone(),
+                        FSEntry::Directory {
+                            children: Vec::new(),
+                            meta: Metadata::dir(node.mode, CONTENT_OWNER, node.mtime),
+                        },
+                    );
+                }
+                NodeData::File(bytes) if node.path == MANIFEST_NAME => {
+                    manifest = String::from_utf8_lossy(&bytes).into_owned();
+                    manifest_mtime = node.mtime;
+                    continue;
+                }
+                NodeData::File(bytes) => {
+                    let meta = Metadata::new(node.mode, CONTENT_OWNER, bytes.len(), node.mtime);
+                    let entry = match String::from_utf8(bytes.into_owned()) {
+                        Ok(content) if !content.contains('\0') => FSEntry::File { content, meta },
+                        _ => FSEntry::Binary { meta },
+                    };
+                    entries.insert(path.clone(), entry);
+                }
+                NodeData::Symlink(target) => {
+                    let meta = Metadata::new(node.mode, CONTENT_OWNER, target.len(), node.mtime);
+                    entries.insert(path.clone(), FSEntry::Symlink { target, meta });
+                }
+            }
 
-        entries.insert(
-            "~/README.md".to_string(),
-            FSEntry::File {
-                content: r#"# Welcome to Uchindami's Portfolio! 👋
+            if let Some((parent, name)) = parent_and_name(&path) {
+                add_child(&mut entries, parent, name)?;
+            }
+        }
+
+        for (rel, description) in content::parse_manifest(&manifest) {
+            let path = child_path(HOME, rel);
+            let Some((parent, name)) = parent_and_name(&path) else {
+                continue;
+            };
+            let meta = match entries.get(&path) {
+                Some(FSEntry::File { meta, .. }) => Metadata {
+                    mode: meta.mode | 0o111,
+                    ..meta.clone()
+                },
+                // Executables are applets of this binary, so report its size
+                _ => {
+                    let size = std::env::current_exe()
+                        .and_then(std::fs::metadata)
+                        .map(|m| m.len() as usize)
+                        .unwrap_or(0);
+                    Metadata::new(0o755, CONTENT_OWNER, size, manifest_mtime)
+                }
+            };
+            let (parent, name) = (parent.to_string(), name.to_string());
+            add_child(&mut entries, &parent, &name)?;
+            entries.insert(path, FSEntry::Executable { description, meta });
+        }
+
+        // Every subdirectory's `..` links back to its parent
+        let subdirs: Vec<String> = entries
+            .iter()
+            .filter(|(_, entry)| entry.is_dir())
+            .filter_map(|(path, _)| parent_and_name(path).map(|(parent, _)| parent.to_string()))
+            .collect();
+        for parent in subdirs {
+            if let Some(FSEntry::Directory { meta, .. }) = entries.get_mut(&parent) {
+                meta.nlink += 1;
+            }
+        }
+
+        if !entries.contains_key(HOME) {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "content tree has no root"));
+        }
+        Ok(Self {
+            base: entries,
+            overlay: HashMap::new(),
+        })
+    }
+
+    /// Load the home directory from a content root on disk
+    pub fn from_dir(root: &std::path::Path) -> io::Result<Self> {
+        Self::from_content(content::read_dir_tree(root)?)
+    }
 
-Hey there! You've just connected to my interactive terminal portfolio.
-This isn't your typical portfolio website - it's a fully functional
-shell environment where you can explore my work like a real filesystem.
+    /// The content tree baked into the binary at build time
+    #[cfg(feature = "embed-content")]
+    pub fn embedded() -> io::Result<Self> {
+        Self::from_content(content::embedded())
+    }
 
-## Quick Start
+    /// Get entry at path
+    pub fn get(&self, path: &str) -> Option<&FSEntry> {
+        match self.overlay.get(path) {
+            Some(entry) => entry.as_ref(),
+            None => self.base.get(path),
+        }
+    }
 
-- `ls` - See all available commands
-- `./dashboard` - Launch the interactive dashboard
-- `./resume` - View my CV/resume
-- `./projects` - Browse my projects
-- `help` - Get detailed help
+    /// Resolve a path typed by the user to the key of the entry it names.
+    /// The path is normalized first, so `..` is lexical (as with `cd -L`);
+    /// symlinks in leading components are then followed, the last one is not.
+    pub fn resolve_path(&self, path: &str, cwd: &str) -> String {
+        let lexical = normalize(path, cwd);
+        self.walk(&lexical, false).unwrap_or(lexical)
+    }
 
-## Easter Eggs 🥚
+    /// Follow symlinks through every component of a resolved path, and
+    /// through the final one too when `follow_last` is set. The result
+    /// names an entry that may not exist; loops give an `ELOOP` error.
+    fn walk(&self, path: &str, follow_last: bool) -> Result<String, String> {
+        let mut resolved = "/".to_string();
+        let mut pending: VecDeque<String> = components(path);
+        let mut hops = 0;
 
-I've hidden some fun surprises throughout. Try exploring different
-commands and see what you find!
+        while let Some(name) = pending.pop_front() {
+            match name.as_str() {
+                "." => continue,
+                ".." => {
+                    resolved = parent(&resolved).to_string();
+                    continue;
+                }
+                _ => {}
+            }
 
-## Tech Stack
+            let candidate = child_path(&resolved, &name);
+            match self.get(&candidate) {
+                Some(FSEntry::Symlink { target, .. }) if follow_last || !pending.is_empty() => {
+                    hops += 1;
+                    if hops > MAX_SYMLINK_HOPS {
+                        return Err(ELOOP.to_string());
+                    }
+                    let target = if target == "~" || target.starts_with("~/") {
+                        resolved = HOME.to_string();
+                        &target[1..]
+                    } else if target.starts_with('/') {
+                        resolved = "/".to_string();
+                        target.as_str()
+                    } else {
+                        target.as_str()
+                    };
+                    for (i, part) in components(target).into_iter().enumerate() {
+                        pending.insert(i, part);
+                    }
+                }
+                _ => resolved = candidate,
+            }
+        }
+        Ok(resolved)
+    }
 
-This portfolio is built with:
-- Rust + Ratatui (TUI framework)
-- Hosted on Google Cloud Run
-- Accessible via web terminal (ttyd)
+    /// The path an entry really lives at, following symlinks all the way
+    pub fn follow(&self, path: &str) -> Result<String, String> {
+        self.walk(path, true)
+    }
 
-Enjoy exploring!
-"#.to_string(),
-                size: 3200,
-                permissions: "-rw-r--r--",
-            },
-        );
+    /// Get the entry at a path, following symlinks (like `stat(2)`)
+    pub fn stat(&self, path: &str) -> Result<&FSEntry, String> {
+        let path = self.follow(path)?;
+        self.get(&path).ok_or_else(|| "No such file or directory".to_string())
+    }
 
-        // Executables
-        entries.insert(
-            "~/about".to_string(),
-            FSEntry::Executable {
-                description: "About me TUI",
-                size: 42000,
-            },
-        );
-        entries.insert(
-            "~/dashboard".to_string(),
-            FSEntry::Executable {
-                description: "Interactive dashboard",
-                size: 38000,
-            },
-        );
-        entries.insert(
-            "~/resume".to_string(),
-            FSEntry::Executable {
-                description: "CV/Resume viewer",
-                size: 45000,
-            },
-        );
-        entries.insert(
-            "~/contact".to_string(),
-            FSEntry::Executable {
-                description: "Contact card",
-                size: 21000,
-            },
-        );
+    // =========================================================================
+    // WRITES - everything below lands in the session overlay
+    // =========================================================================
 
-        // Subdirectories
-        entries.insert(
-            "~/blog".to_string(),
-            FSEntry::Directory {
-                children: vec!["2026".to_string(), "2025".to_string()],
-            },
-        );
-        entries.insert(
-            "~/blog/2026".to_string(),
-            FSEntry::Directory {
-                children: vec![
-                    "building-portfolio-in-rust.md".to_string(),
-                    "mastering-async-rust.md".to_string(),
-                ],
+    /// Store an entry in the overlay, enforcing the session quotas. Only
+    /// the home directory is writable.
+    fn put(&mut self, path: &str, entry: FSEntry) -> Result<(), String> {
+        if !in_home(path) {
+            return Err("Permission denied".to_string());
+        }
+        let (mut bytes, mut count) = self.overlay_usage();
+        if let Some(Some(old)) = self.overlay.get(path) {
+            bytes -= entry_bytes(old);
+            count -= 1;
+        }
+        if bytes + entry_bytes(&entry) > MAX_OVERLAY_BYTES || count + 1 > MAX_OVERLAY_ENTRIES {
+            return Err("Disk quota exceeded".to_string());
+        }
+        self.overlay.insert(path.to_string(), Some(entry));
+        Ok(())
+    }
+
+    /// Hide an entry. Never fails, so a full session can always free space
+    fn delete(&mut self, path: &str) {
+        if self.base.contains_key(path) {
+            self.overlay.insert(path.to_string(), None);
+        } else {
+            self.overlay.remove(path);
+        }
+    }
+
+    /// Bytes and live entries currently held by the overlay
+    fn overlay_usage(&self) -> (usize, usize) {
+        self.overlay
+            .values()
+            .flatten()
+            .fold((0, 0), |(bytes, count), entry| (bytes + entry_bytes(entry), count + 1))
+    }
+
+    /// Replace a directory's children, updating its link count and mtime
+    fn set_children(&mut self, path: &str, children: Vec<String>) -> Result<(), String> {
+        let Some(FSEntry::Directory { meta, .. }) = self.get(path) else {
+            return Ok(());
+        };
+        let subdirs = children
+            .iter()
+            .filter(|c| self.get(&child_path(path, c)).is_some_and(FSEntry::is_dir))
+            .count();
+        let meta = Metadata {
+            nlink: 2 + subdirs,
+            mtime: time::now(),
+            ..meta.clone()
+        };
+        self.put(path, FSEntry::Directory { children, meta })
+    }
+
+    /// Add `name` to the directory at `parent`
+    fn link(&mut self, parent: &str, name: &str) -> Result<(), String> {
+        match self.get(parent) {
+            Some(FSEntry::Directory { children, .. }) => {
+                let mut children = children.clone();
+                if !children.iter().any(|c| c == name) {
+                    children.push(name.to_string());
+                    children.sort();
+                }
+                self.set_children(parent, children)
+            }
+            Some(_) => Err("Not a directory".to_string()),
+            None => Err("No such file or directory".to_string()),
+        }
+    }
+
+    /// Store an entry and register it with its parent directory
+    fn insert(&mut self, path: &str, entry: FSEntry) -> Result<(), String> {
+        let (parent, name) = split_path(path)?;
+        match self.get(parent) {
+            Some(FSEntry::Directory { .. }) => {}
+            Some(_) => return Err("Not a directory".to_string()),
+            None => return Err("No such file or directory".to_string()),
+        }
+        self.put(path, entry)?;
+        self.link(parent, name).inspect_err(|_| self.delete(path))
+    }
+
+    /// Remove `name` from the directory at `parent`
+    fn unlink(&mut self, parent: &str, name: &str) -> Result<(), String> {
+        if let Some(FSEntry::Directory { children, .. }) = self.get(parent) {
+            let children = children.iter().filter(|c| *c != name).cloned().collect();
+            self.set_children(parent, children)?;
+        }
+        Ok(())
+    }
+
+    /// Create or overwrite a file, or append to it. Writing through a
+    /// symlink writes its target.
+    pub fn write_file(&mut self, path: &str, content: &str, append: bool) -> Result<(), String> {
+        let path = &self.follow(path)?;
+        let new_content = match self.get(path) {
+            Some(FSEntry::File { content: existing, .. }) if append => {
+                format!("{}{}", existing, content)
+            }
+            Some(FSEntry::Binary { .. }) if append => return Err("binary file".to_string()),
+            Some(FSEntry::File { .. } | FSEntry::Binary { .. }) | None => content.to_string(),
+            Some(FSEntry::Directory { .. }) => return Err("Is a directory".to_string()),
+            Some(FSEntry::Executable { .. }) => return Err("Permission denied".to_string()),
+            // `follow` already resolved any link
+            Some(FSEntry::Symlink { .. }) => return Err(ELOOP.to_string()),
+        };
+        let meta = match self.get(path) {
+            Some(FSEntry::File { meta, .. } | FSEntry::Binary { meta }) => Metadata {
+                size: new_content.len(),
+                mtime: time::now(),
+                ..meta.clone()
             },
-        );
-        entries.insert(
-            "~/blog/2026/building-portfolio-in-rust.md".to_string(),
+            _ => Metadata::new(0o644, SESSION_OWNER, new_content.len(), time::now()),
+        };
+
+        self.insert(
+            path,
             FSEntry::File {
-                content: "# Building a Portfolio in Rust\n\nComing soon...".to_string(),
-                size: 1200,
-                permissions: "-rw-r--r--",
+                content: new_content,
+                meta,
             },
-        );
+        )
+    }
 
-        entries.insert(
-            "~/downloads".to_string(),
+    /// Create an empty file, or bump the mtime of an existing entry
+    pub fn touch(&mut self, path: &str) -> Result<(), String> {
+        let path = &self.follow(path)?;
+        match self.get(path).cloned() {
+            Some(mut entry) => {
+                entry.meta_mut().mtime = time::now();
+                self.put(path, entry)
+            }
+            None => self.write_file(path, "", false),
+        }
+    }
+
+    /// Create a directory; its parent must already exist
+    pub fn create_dir(&mut self, path: &str) -> Result<(), String> {
+        if self.get(path).is_some() {
+            return Err("File exists".to_string());
+        }
+        self.insert(
+            path,
             FSEntry::Directory {
-                children: vec!["resume.pdf".to_string(), "resume.tex".to_string()],
+                children: Vec::new(),
+                meta: Metadata::dir(0o755, SESSION_OWNER, time::now()),
             },
-        );
+        )
+    }
+
+    /// Create a symbolic link at `path` pointing to `target`
+    pub fn symlink(&mut self, target: &str, path: &str) -> Result<(), String> {
+        if self.get(path).is_some() {
+            return Err("File exists".to_string());
+        }
+        let meta = Metadata::new(0o777, SESSION_OWNER, target.len(), time::now());
+        self.insert(
+            path,
+            FSEntry::Symlink {
+                target: target.to_string(),
+                meta,
+            },
+        )
+    }
 
-        Self { entries }
+    /// Remove a file, or a directory and everything in it when `recursive`
+    pub fn remove(&mut self, path: &str, recursive: bool) -> Result<(), String> {
+        let (parent, name) = split_path(path)?;
+        if !in_home(parent) {
+            return Err("Permission denied".to_string());
+        }
+        match self.get(path) {
+            None => return Err("No such file or directory".to_string()),
+            Some(FSEntry::Directory { .. }) if !recursive => {
+                return Err("Is a directory".to_string());
+            }
+            Some(_) => {}
+        }
+        for descendant in self.descendants(path) {
+            self.delete(&descendant);
+        }
+        self.delete(path);
+        self.unlink(parent, name)
     }
 
-    /// Get entry at path
-    pub fn get(&self, path: &str) -> Option<&FSEntry> {
-        self.entries.get(path)
+    /// Copy a file, or a whole directory when `recursive`. Copies belong
+    /// to the visitor and are stamped with the current time.
+    pub fn copy(&mut self, from: &str, to: &str, recursive: bool) -> Result<(), String> {
+        self.copy_tree(from, to, recursive, false)
     }
 
-    /// Resolve relative path to absolute
-    pub fn resolve_path(&self, path: &str, cwd: &str) -> String {
-        if path.starts_with("~/") || path == "~" {
-            path.to_string()
-        } else if path.starts_with("./") {
-            format!("{}/{}", cwd, &path[2..])
-        } else if path == ".." {
-            if cwd == "~" {
-                "~".to_string()
-            } else {
-                let parts: Vec<&str> = cwd.rsplitn(2, '/').collect();
-                if parts.len() > 1 {
-                    parts[1].to_string()
-                } else {
-                    "~".to_string()
+    /// Copy an entry and everything below it, keeping the original
+    /// metadata when `preserve` is set
+    fn copy_tree(
+        &mut self,
+        from: &str,
+        to: &str,
+        recursive: bool,
+        preserve: bool,
+    ) -> Result<(), String> {
+        let stamp = |mut entry: FSEntry| {
+            if !preserve {
+                let meta = entry.meta_mut();
+                meta.owner = SESSION_OWNER;
+                meta.group = SESSION_OWNER;
+                meta.mtime = time::now();
+            }
+            entry
+        };
+        let entry = self.get(from).cloned().ok_or("No such file or directory")?;
+        if let FSEntry::Directory { .. } = entry {
+            if !recursive {
+                return Err("Is a directory".to_string());
+            }
+            if to == from || to.starts_with(&format!("{}/", from)) {
+                return Err("cannot copy a directory into itself".to_string());
+            }
+        }
+        if let Some(FSEntry::Directory { .. }) = self.get(to)
+            && !matches!(entry, FSEntry::Directory { .. })
+        {
+            return Err("Is a directory".to_string());
+        }
+
+        let descendants = self.descendants(from);
+        self.insert(to, stamp(entry))?;
+        for descendant in descendants {
+            if let Some(entry) = self.get(&descendant).cloned() {
+                let target = format!("{}{}", to, &descendant[from.len()..]);
+                self.put(&target, stamp(entry))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Move or rename an entry, replacing a file at `to`. The quota is
+    /// checked up front so a move never stops halfway through a tree.
+    pub fn rename(&mut self, from: &str, to: &str) -> Result<(), String> {
+        let Some(entry) = self.get(from) else {
+            return Err("No such file or directory".to_string());
+        };
+        if to == from {
+            return Ok(());
+        }
+        if to.starts_with(&format!("{}/", from)) {
+            return Err("cannot move a directory into itself".to_string());
+        }
+        match self.get(to) {
+            Some(FSEntry::Directory { .. }) => return Err("Is a directory".to_string()),
+            Some(_) if entry.is_dir() => return Err("cannot overwrite non-directory".to_string()),
+            _ => {}
+        }
+        if !in_home(to) || !in_home(parent(from)) {
+            return Err("Permission denied".to_string());
+        }
+
+        // The copy exists alongside the original until it is removed, plus
+        // the target's parent directory may join the overlay
+        let (bytes, count) = self.overlay_usage();
+        let (moved_bytes, moved_count) = std::iter::once(from.to_string())
+            .chain(self.descendants(from))
+            .filter_map(|path| self.get(&path))
+            .fold((0, 0), |(bytes, count), entry| (bytes + entry_bytes(entry), count + 1));
+        let (old_bytes, old_count) = match self.overlay.get(to) {
+            Some(Some(old)) => (entry_bytes(old), 1),
+            _ => (0, 0),
+        };
+        if bytes + moved_bytes - old_bytes > MAX_OVERLAY_BYTES
+            || count + moved_count + 1 - old_count > MAX_OVERLAY_ENTRIES
+        {
+            return Err("Disk quota exceeded".to_string());
+        }
+
+        if self.get(to).is_some() {
+            self.remove(to, true)?;
+        }
+        self.copy_tree(from, to, true, true)?;
+        self.remove(from, true)
+    }
+
+    /// Paths of every entry below a directory
+    fn descendants(&self, path: &str) -> Vec<String> {
+        let mut out = Vec::new();
+        if let Some(entries) = self.list_dir(path) {
+            for (name, entry) in entries {
+                let child = child_path(path, &name);
+                if let FSEntry::Directory { .. } = entry {
+                    out.extend(self.descendants(&child));
                 }
+                out.push(child);
             }
-        } else if path == "." {
-            cwd.to_string()
-        } else {
-            format!("{}/{}", cwd, path)
         }
+        out
     }
 
     /// List directory contents
     pub fn list_dir(&self, path: &str) -> Option<Vec<(String, &FSEntry)>> {
-        if let Some(FSEntry::Directory { children }) = self.get(path) {
+        if let Some(FSEntry::Directory { children, .. }) = self.get(path) {
             let mut result = Vec::new();
             for child in children {
-                let child_path = format!("{}/{}", path, child);
-                if let Some(entry) = self.get(&child_path) {
+                if let Some(entry) = self.get(&child_path(path, child)) {
                     result.push((child.clone(), entry));
                 }
             }
@@ -210,40 +630,259 @@ Enjoy exploring!
             None
         }
     }
+}
 
-    /// Tab completion
-    pub fn complete(&self, input: &str, cwd: &str) -> Vec<String> {
-        let mut completions = Vec::new();
+/// Non-empty components of a `/`-separated path
+fn components(path: &str) -> VecDeque<String> {
+    path.split('/').filter(|c| !c.is_empty()).map(str::to_string).collect()
+}
 
-        // Get the part after the last space (the argument being completed)
-        let parts: Vec<&str> = input.rsplitn(2, ' ').collect();
-        let (prefix, partial) = if parts.len() == 2 {
-            (parts[1], parts[0])
+/// Turn a path as typed into an absolute, normalized one: `~` stands for
+/// the home directory, empty and `.` components vanish, `..` drops the
+/// previous component (never climbing above `/`), and trailing slashes go.
+/// Relative paths start from `cwd`. Symlinks are not consulted.
+pub fn normalize(path: &str, cwd: &str) -> String {
+    let expand_tilde = |path: &str| {
+        if path == "~" || path.starts_with("~/") {
+            format!("{}{}", HOME, &path[1..])
         } else {
-            ("", parts[0])
-        };
+            path.to_string()
+        }
+    };
+    let path = expand_tilde(path);
+    let full = if path.starts_with('/') {
+        path
+    } else {
+        format!("{}/{}", expand_tilde(cwd), path)
+    };
 
-        // List current directory and find matches
-        if let Some(entries) = self.list_dir(cwd) {
-            for (name, entry) in entries {
-                let display_name = match entry {
-                    FSEntry::Directory { .. } => format!("{}/", name),
-                    FSEntry::Executable { .. } => format!("./{}", name),
-                    FSEntry::File { .. } => name.clone(),
-                };
-
-                if display_name.starts_with(partial) || name.starts_with(partial) {
-                    let completion = if prefix.is_empty() {
-                        display_name
-                    } else {
-                        format!("{} {}", prefix, display_name)
-                    };
-                    completions.push(completion);
-                }
+    let mut stack = Vec::new();
+    for component in full.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                stack.pop();
             }
+            name => stack.push(name),
         }
+    }
+    format!("/{}", stack.join("/"))
+}
+
+/// A normalized path for display, with the home directory shown as `~`
+pub fn display_path(path: &str) -> String {
+    match path.strip_prefix(HOME) {
+        Some("") => "~".to_string(),
+        Some(rest) if rest.starts_with('/') => format!("~{}", rest),
+        _ => path.to_string(),
+    }
+}
+
+/// Path of `name` inside the directory at `parent`
+pub fn child_path(parent: &str, name: &str) -> String {
+    if name.is_empty() {
+        parent.to_string()
+    } else if parent.ends_with('/') {
+        format!("{}{}", parent, name)
+    } else {
+        format!("{}/{}", parent, name)
+    }
+}
+
+/// Parent directory of a normalized path; the root is its own parent
+pub fn parent(path: &str) -> &str {
+    parent_and_name(path).map_or("/", |(parent, _)| parent)
+}
+
+/// Split a normalized path into its parent directory and final name.
+/// The root has neither.
+fn parent_and_name(path: &str) -> Option<(&str, &str)> {
+    match path.rsplit_once('/') {
+        Some((_, "")) | None => None,
+        Some(("", name)) => Some(("/", name)),
+        Some((parent, name)) => Some((parent, name)),
+    }
+}
+
+/// Whether a path is the home directory or inside it
+fn in_home(path: &str) -> bool {
+    path.strip_prefix(HOME).is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Split a path into its parent directory and final name
+fn split_path(path: &str) -> Result<(&str, &str), String> {
+    // Nothing can replace the root
+    parent_and_name(path).ok_or_else(|| "Permission denied".to_string())
+}
+
+/// Content bytes an entry counts against the session quota
+fn entry_bytes(entry: &FSEntry) -> usize {
+    match entry {
+        FSEntry::File { content, .. } => content.len(),
+        FSEntry::Directory { children, .. } => children.iter().map(String::len).sum(),
+        FSEntry::Binary { .. } | FSEntry::Executable { .. } => 0,
+        FSEntry::Symlink { target, .. } => target.len(),
+    }
+}
+
+/// Register `name` as a child of the directory at `parent`
+fn add_child(entries: &mut HashMap<String, FSEntry>, parent: &str, name: &str) -> io::Result<()> {
+    match entries.get_mut(parent) {
+        Some(FSEntry::Directory { children, .. }) => {
+            if !children.iter().any(|c| c == name) {
+                children.push(name.to_string());
+                children.sort();
+            }
+            Ok(())
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{}: not a directory", parent),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CWD: &str = "/home/guest/blog";
+
+    fn node(path: &str, data: NodeData) -> ContentNode {
+        ContentNode {
+            path: path.to_string(),
+            data,
+            mode: 0o755,
+            mtime: 0,
+        }
+    }
 
-        completions.sort();
-        completions
+    /// ~/README.md, ~/blog/2026/post.md, and links ~/posts -> blog/2026, ~/loop -> loop
+    fn sample_fs() -> VirtualFS {
+        VirtualFS::from_content(vec![
+            node("", NodeData::Dir),
+            node("README.md", NodeData::File(b"hello".as_slice().into())),
+            node("blog", NodeData::Dir),
+            node("blog/2026", NodeData::Dir),
+            node("blog/2026/post.md", NodeData::File(b"post".as_slice().into())),
+            node("posts", NodeData::Symlink("blog/2026".to_string())),
+            node("loop", NodeData::Symlink("loop".to_string())),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn normalize_relative_paths() {
+        assert_eq!(normalize("2026", CWD), "/home/guest/blog/2026");
+        assert_eq!(normalize("./2026/post.md", CWD), "/home/guest/blog/2026/post.md");
+        assert_eq!(normalize(".", CWD), CWD);
+        assert_eq!(normalize("", CWD), CWD);
+    }
+
+    #[test]
+    fn normalize_dot_dot() {
+        assert_eq!(normalize("..", CWD), HOME);
+        assert_eq!(normalize("../blog", CWD), CWD);
+        assert_eq!(normalize("2026/../../README.md", CWD), "/home/guest/README.md");
+    }
+
+    #[test]
+    fn normalize_clamps_at_root() {
+        assert_eq!(normalize("../../../..", CWD), "/");
+        assert_eq!(normalize("/..", CWD), "/");
+        assert_eq!(normalize("/../home/./guest", CWD), HOME);
+    }
+
+    #[test]
+    fn normalize_slashes() {
+        assert_eq!(normalize("2026//", CWD), "/home/guest/blog/2026");
+        assert_eq!(normalize("//home///guest/", CWD), HOME);
+        assert_eq!(normalize("/", CWD), "/");
+    }
+
+    #[test]
+    fn normalize_home_alias() {
+        assert_eq!(normalize("~", CWD), HOME);
+        assert_eq!(normalize("~/", CWD), HOME);
+        assert_eq!(normalize("~/blog/..", "/"), HOME);
+        assert_eq!(normalize("README.md", "~"), "/home/guest/README.md");
+        // Only a leading `~` on its own is the home directory
+        assert_eq!(normalize("~user", HOME), "/home/guest/~user");
+        assert_eq!(normalize("a/~", HOME), "/home/guest/a/~");
+    }
+
+    #[test]
+    fn display_path_abbreviates_home() {
+        assert_eq!(display_path(HOME), "~");
+        assert_eq!(display_path("/home/guest/blog"), "~/blog");
+        assert_eq!(display_path("/home/guestbook"), "/home/guestbook");
+        assert_eq!(display_path("/"), "/");
+    }
+
+    #[test]
+    fn parent_of_paths() {
+        assert_eq!(parent("/home/guest"), "/home");
+        assert_eq!(parent("/home"), "/");
+        assert_eq!(parent("/"), "/");
+    }
+
+    #[test]
+    fn resolve_path_follows_leading_symlinks() {
+        let fs = sample_fs();
+        assert_eq!(fs.resolve_path("posts/post.md", HOME), "/home/guest/blog/2026/post.md");
+        assert_eq!(fs.resolve_path("~/posts/", "/"), "/home/guest/posts");
+        assert_eq!(fs.follow("/home/guest/posts").unwrap(), "/home/guest/blog/2026");
+        assert!(fs.stat("/home/guest/posts").unwrap().is_dir());
+    }
+
+    #[test]
+    fn symlink_loops_are_detected() {
+        let fs = sample_fs();
+        assert_eq!(fs.stat("/home/guest/loop").err().as_deref(), Some(ELOOP));
+        assert!(matches!(fs.get("/home/guest/loop"), Some(FSEntry::Symlink { .. })));
+    }
+
+    #[test]
+    fn writes_stay_in_home() {
+        let mut fs = sample_fs();
+        assert!(fs.write_file("/home/guest/notes.txt", "hi", false).is_ok());
+        assert!(fs.write_file("/notes.txt", "hi", false).is_err());
+        assert!(fs.create_dir("/home/other").is_err());
+        assert!(fs.remove(HOME, true).is_err());
+    }
+
+    #[test]
+    fn rename_moves_a_whole_tree() {
+        let mut fs = sample_fs();
+        fs.rename("/home/guest/blog", "/home/guest/journal").unwrap();
+        assert!(fs.get("/home/guest/blog").is_none());
+        assert!(fs.get("/home/guest/blog/2026/post.md").is_none());
+        assert!(fs.get("/home/guest/journal/2026/post.md").is_some());
+    }
+
+    #[test]
+    fn rename_never_turns_a_file_into_a_directory() {
+        let mut fs = sample_fs();
+        assert_eq!(
+            fs.rename("/home/guest/blog", "/home/guest/README.md"),
+            Err("cannot overwrite non-directory".to_string())
+        );
+        assert!(!fs.get("/home/guest/README.md").unwrap().is_dir());
+        assert!(fs.get("/home/guest/blog/2026/post.md").is_some());
+    }
+
+    #[test]
+    fn rename_checks_the_quota_before_moving() {
+        let mut fs = sample_fs();
+        fs.create_dir("/home/guest/big").unwrap();
+        for i in 0..MAX_OVERLAY_ENTRIES / 2 {
+            fs.write_file(&format!("/home/guest/big/{}", i), "x", false).unwrap();
+        }
+        assert_eq!(
+            fs.rename("/home/guest/big", "/home/guest/bigger"),
+            Err("Disk quota exceeded".to_string())
+        );
+        assert!(fs.get("/home/guest/bigger").is_none());
+        assert!(fs.get("/home/guest/big/0").is_some());
     }
 }
@@ -0,0 +1,391 @@
+//! Wildcard expansion (`*`, `?`, `[abc]`, `**`) against the virtual filesystem
+
+use std::collections::HashSet;
+
+use super::filesystem::{normalize, HOME};
+use super::{FSEntry, VirtualFS};
+
+/// Whether a pattern has any unescaped wildcard characters
+pub fn has_wildcards(pattern: &str) -> bool {
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '*' | '?' | '[' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Remove backslash escapes from a pattern without wildcards
+fn unescape(pattern: &str) -> String {
+    let mut out = String::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.extend(chars.next()),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Match a `[...]` class against `c`. Returns the match result and the
+/// rest of the pattern, or `None` if the bracket is never closed.
+fn match_class(pattern: &[char], c: char) -> Option<(bool, &[char])> {
+    let (negate, mut i) = match pattern.first() {
+        Some('!') | Some('^') => (true, 1),
+        _ => (false, 0),
+    };
+    let mut matched = false;
+    let mut first = true;
+
+    while i < pattern.len() {
+        let mut lo = pattern[i];
+        if lo == ']' && !first {
+            return Some((matched != negate, &pattern[i + 1..]));
+        }
+        first = false;
+        if lo == '\\' && i + 1 < pattern.len() {
+            i += 1;
+            lo = pattern[i];
+        }
+        if i + 2 < pattern.len() && pattern[i + 1] == '-' && pattern[i + 2] != ']' {
+            let hi = pattern[i + 2];
+            matched |= lo <= c && c <= hi;
+            i += 3;
+        } else {
+            matched |= lo == c;
+            i += 1;
+        }
+    }
+    None
+}
+
+/// Match the single-character piece at the start of `pattern` (anything
+/// but `*`) against `c`. Returns how much of the pattern it took.
+fn match_one(pattern: &[char], c: char) -> Option<usize> {
+    match pattern.first()? {
+        '*' => None,
+        '?' => Some(1),
+        '[' => match match_class(&pattern[1..], c) {
+            Some((true, rest)) => Some(pattern.len() - rest.len()),
+            Some((false, _)) => None,
+            // An unclosed `[` is matched literally
+            None => (c == '[').then_some(1),
+        },
+        '\\' if pattern.len() > 1 => (pattern[1] == c).then_some(2),
+        &p => (p == c).then_some(1),
+    }
+}
+
+/// Greedy matcher: a `*` first takes nothing, and takes one more character
+/// each time the rest fails to match. Only the last `*` is ever retried,
+/// since an earlier one taking more could not help, so a match takes at
+/// most pattern length times name length steps.
+fn match_chars(pattern: &[char], name: &[char]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    // Pattern position after the last `*`, and how far into the name it reaches
+    let mut star = None;
+    while n < name.len() {
+        if pattern.get(p) == Some(&'*') {
+            p += 1;
+            star = Some((p, n));
+        } else if let Some(len) = match_one(&pattern[p..], name[n]) {
+            p += len;
+            n += 1;
+        } else if let Some((after, reach)) = star {
+            p = after;
+            n = reach + 1;
+            star = Some((after, n));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Match a single path component against a pattern
+pub fn matches(pattern: &str, name: &str) -> bool {
+    // Hidden files only match patterns that start with a literal dot
+    if name.starts_with('.') && !pattern.starts_with('.') {
+        return false;
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    match_chars(&pattern, &name)
+}
+
+/// Match a whole name, dotfiles included, the way `find -name` does
+pub fn matches_name(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    match_chars(&pattern, &name)
+}
+
+fn join(base: &str, name: &str) -> String {
+    if base.is_empty() {
+        name.to_string()
+    } else if base.ends_with('/') {
+        format!("{}{}", base, name)
+    } else {
+        format!("{}/{}", base, name)
+    }
+}
+
+/// The entries of the directory at `path`, following symlinks to it
+fn list(fs: &VirtualFS, path: &str) -> Vec<String> {
+    let entries = fs.follow(path).ok().and_then(|path| fs.list_dir(&path));
+    entries.unwrap_or_default().into_iter().map(|(name, _)| name).collect()
+}
+
+/// Whether `path` is a directory or a symlink to one
+fn is_dir(fs: &VirtualFS, path: &str) -> bool {
+    fs.stat(path).is_ok_and(FSEntry::is_dir)
+}
+
+/// Every entry below a directory, depth first, going through symlinked
+/// directories. Hidden entries are skipped. `visited` holds the real
+/// paths of the directories already listed, so a link loop ends.
+fn descendants(
+    fs: &VirtualFS,
+    display: &str,
+    path: &str,
+    visited: &mut HashSet<String>,
+    out: &mut Vec<(String, String)>,
+) {
+    if !fs.follow(path).is_ok_and(|real| visited.insert(real)) {
+        return;
+    }
+    for name in list(fs, path) {
+        if name.starts_with('.') {
+            continue;
+        }
+        let child = (join(display, &name), join(path, &name));
+        out.push(child.clone());
+        if is_dir(fs, &child.1) {
+            descendants(fs, &child.0, &child.1, visited, out);
+        }
+    }
+}
+
+/// Expand a pattern into matching paths, written the way the pattern was
+/// (relative patterns give relative results). Returns nothing if no entry matches.
+pub fn expand(pattern: &str, fs: &VirtualFS, cwd: &str) -> Vec<String> {
+    let (display, base, rest) = if pattern == "~" {
+        ("~", HOME, "")
+    } else if let Some(rest) = pattern.strip_prefix("~/") {
+        ("~", HOME, rest)
+    } else if let Some(rest) = pattern.strip_prefix('/') {
+        ("/", "/", rest)
+    } else {
+        ("", cwd, pattern)
+    };
+
+    let dirs_only = rest.ends_with('/');
+    let components: Vec<&str> = rest.split('/').filter(|c| !c.is_empty()).collect();
+    let mut candidates = vec![(display.to_string(), base.to_string())];
+
+    for (i, component) in components.iter().enumerate() {
+        let is_last = i == components.len() - 1;
+        let mut next = Vec::new();
+
+        for (display, path) in &candidates {
+            if *component == "**" {
+                // Zero or more directories; as the last component, everything below
+                next.push((display.clone(), path.clone()));
+                let mut below = Vec::new();
+                descendants(fs, display, path, &mut HashSet::new(), &mut below);
+                next.extend(below.into_iter().filter(|(_, p)| is_last || is_dir(fs, p)));
+            } else if has_wildcards(component) {
+                for name in list(fs, path) {
+                    if matches(component, &name) {
+                        next.push((join(display, &name), join(path, &name)));
+                    }
+                }
+            } else if *component == "." || *component == ".." {
+                next.push((join(display, component), normalize(component, path)));
+            } else {
+                let name = unescape(component);
+                let child = (join(display, &name), join(path, &name));
+                // Links on the way are followed, a dangling one at the end still exists
+                if fs.get(&fs.resolve_path(&child.1, "/")).is_some() {
+                    next.push(child);
+                }
+            }
+        }
+        candidates = next;
+    }
+
+    let mut results: Vec<String> = candidates
+        .into_iter()
+        .filter(|(display, path)| !display.is_empty() && (!dirs_only || is_dir(fs, path)))
+        .map(|(display, _)| if dirs_only { format!("{}/", display) } else { display })
+        .collect();
+    results.sort();
+    results.dedup();
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::content::{ContentNode, NodeData};
+    use super::*;
+
+    const CWD: &str = "/home/guest/blog";
+
+    fn node(path: &str, data: NodeData) -> ContentNode {
+        ContentNode {
+            path: path.to_string(),
+            data,
+            mode: 0o644,
+            mtime: 0,
+        }
+    }
+
+    fn file(path: &str) -> ContentNode {
+        node(path, NodeData::File(b"".as_slice().into()))
+    }
+
+    /// ~/README.md, ~/.hidden, ~/blog/{a.md,b.md,c.txt,[x].md} and ~/blog/2026/post.md
+    fn sample_fs() -> VirtualFS {
+        VirtualFS::from_content(vec![
+            node("", NodeData::Dir),
+            file(".hidden"),
+            file("README.md"),
+            node("blog", NodeData::Dir),
+            file("blog/a.md"),
+            file("blog/b.md"),
+            file("blog/c.txt"),
+            file("blog/[x].md"),
+            node("blog/2026", NodeData::Dir),
+            file("blog/2026/post.md"),
+        ])
+        .unwrap()
+    }
+
+    /// The sample tree with ~/latest -> blog/2026 and a loop ~/blog/2026/up -> ..
+    fn linked_fs() -> VirtualFS {
+        VirtualFS::from_content(vec![
+            node("", NodeData::Dir),
+            node("blog", NodeData::Dir),
+            file("blog/a.md"),
+            node("blog/2026", NodeData::Dir),
+            file("blog/2026/post.md"),
+            node("blog/2026/up", NodeData::Symlink("..".to_string())),
+            node("latest", NodeData::Symlink("blog/2026".to_string())),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn star_and_question_mark() {
+        assert!(matches("*.md", "post.md"));
+        assert!(matches("*", "post.md"));
+        assert!(matches("p*t.m?", "post.md"));
+        assert!(matches("*.*", "a.b.c"));
+        assert!(!matches("*.md", "post.txt"));
+        assert!(!matches("?", ""));
+        assert!(!matches("p?", "p"));
+        assert!(matches("a**b", "ab"));
+    }
+
+    #[test]
+    fn classes() {
+        assert!(matches("[abc].md", "b.md"));
+        assert!(!matches("[abc].md", "d.md"));
+        assert!(matches("[!abc]", "d"));
+        assert!(matches("[^abc]", "d"));
+        assert!(!matches("[!abc]", "a"));
+        assert!(matches("[a-c]x", "bx"));
+        assert!(!matches("[a-c]x", "dx"));
+        // `]` first is a member, and `-` last is literal
+        assert!(matches("[]a]", "]"));
+        assert!(matches("[a-]", "-"));
+        // An unclosed bracket is literal
+        assert!(matches("[ab", "[ab"));
+        assert!(!matches("[ab", "a"));
+    }
+
+    #[test]
+    fn escapes_are_literal() {
+        assert!(matches("\\*", "*"));
+        assert!(!matches("\\*", "a"));
+        assert!(matches("a\\?", "a?"));
+        assert!(matches("[\\]]", "]"));
+        assert!(!has_wildcards("a\\*b"));
+        assert!(has_wildcards("a\\**"));
+    }
+
+    #[test]
+    fn hidden_names_need_a_dot() {
+        assert!(!matches("*", ".hidden"));
+        assert!(!matches("?hidden", ".hidden"));
+        assert!(matches(".*", ".hidden"));
+        assert!(matches_name("*", ".hidden"));
+    }
+
+    #[test]
+    fn many_stars_stay_fast() {
+        let name = "a".repeat(200);
+        let pattern = format!("{}b", "*a".repeat(12));
+        assert!(!matches(&pattern, &name));
+        assert!(matches(&"*a".repeat(12), &name));
+    }
+
+    #[test]
+    fn expand_relative_and_absolute() {
+        let fs = sample_fs();
+        assert_eq!(expand("*.md", &fs, CWD), ["[x].md", "a.md", "b.md"]);
+        assert_eq!(expand("[ab].md", &fs, CWD), ["a.md", "b.md"]);
+        assert_eq!(expand("~/*", &fs, CWD), ["~/README.md", "~/blog"]);
+        assert_eq!(expand("/home/guest/blog/*.txt", &fs, CWD), ["/home/guest/blog/c.txt"]);
+        assert_eq!(expand("../b*/c.*", &fs, CWD), ["../blog/c.txt"]);
+        assert_eq!(expand("*/", &fs, CWD), ["2026/"]);
+    }
+
+    #[test]
+    fn expand_double_star() {
+        let fs = sample_fs();
+        assert_eq!(
+            expand("~/**/*.md", &fs, CWD),
+            ["~/README.md", "~/blog/2026/post.md", "~/blog/[x].md", "~/blog/a.md", "~/blog/b.md"]
+        );
+        assert_eq!(
+            expand("**", &fs, CWD),
+            ["2026", "2026/post.md", "[x].md", "a.md", "b.md", "c.txt"]
+        );
+    }
+
+    #[test]
+    fn expand_without_matches() {
+        let fs = sample_fs();
+        assert!(expand("*.pdf", &fs, CWD).is_empty());
+        assert!(expand("nope/*", &fs, CWD).is_empty());
+        // Escaped wildcards name the file literally
+        assert_eq!(expand("\\[x\\].md", &fs, CWD), ["[x].md"]);
+    }
+
+    #[test]
+    fn expand_through_symlinks() {
+        let fs = linked_fs();
+        assert_eq!(expand("~/latest/*.md", &fs, CWD), ["~/latest/post.md"]);
+        assert_eq!(expand("~/latest/up/*.md", &fs, CWD), ["~/latest/up/a.md"]);
+        assert_eq!(expand("~/l*/", &fs, CWD), ["~/latest/"]);
+        assert_eq!(expand("~/latest/post.m?", &fs, CWD), ["~/latest/post.md"]);
+    }
+
+    #[test]
+    fn double_star_survives_link_loops() {
+        let fs = linked_fs();
+        // Each directory is listed once, though its links still match
+        assert_eq!(
+            expand("~/**/*.md", &fs, CWD),
+            ["~/blog/2026/post.md", "~/blog/2026/up/a.md", "~/blog/a.md", "~/latest/post.md"]
+        );
+        assert_eq!(expand("~/**", &fs, CWD).len(), 7);
+    }
+}
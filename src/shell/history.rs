@@ -0,0 +1,146 @@
+//! Command history - numbering, search and `!` expansion
+
+/// Entries kept before the oldest are dropped
+const MAX_HISTORY: usize = 100;
+
+/// Lines entered this session, oldest first
+pub struct History {
+    entries: Vec<String>,
+    /// History number of `entries[0]`; grows as old entries are dropped
+    first_number: usize,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            first_number: 1,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Record a line, unless it repeats the previous one
+    pub fn push(&mut self, line: &str) {
+        if self.entries.last().is_some_and(|last| last == line) {
+            return;
+        }
+        self.entries.push(line.to_string());
+        if self.entries.len() > MAX_HISTORY {
+            self.entries.remove(0);
+            self.first_number += 1;
+        }
+    }
+
+    /// Forget every entry. Numbering carries on, as in bash.
+    pub fn clear(&mut self) {
+        self.first_number += self.entries.len();
+        self.entries.clear();
+    }
+
+    /// Entries with their history numbers, oldest first
+    pub fn numbered(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(i, line)| (self.first_number + i, line.as_str()))
+    }
+
+    /// Index of the newest entry before `before` that contains `query`
+    pub fn search(&self, query: &str, before: usize) -> Option<usize> {
+        self.entries[..before.min(self.entries.len())]
+            .iter()
+            .rposition(|line| line.contains(query))
+    }
+
+    /// Expand `!!`, `!n`, `!-n` and `!prefix` references. Returns `None`
+    /// when the line has none, so the caller knows whether to echo it.
+    pub fn expand(&self, line: &str) -> Result<Option<String>, String> {
+        let mut out = String::new();
+        let mut expanded = false;
+        let mut in_single = false;
+        let mut chars = line.char_indices().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\'' => in_single = !in_single,
+                '\\' if !in_single => {
+                    out.push(c);
+                    if let Some((_, next)) = chars.next() {
+                        out.push(next);
+                    }
+                    continue;
+                }
+                '!' if !in_single => {
+                    // `!` before a blank, `=` or `(` is just a character
+                    let designator: String = match chars.peek() {
+                        None => String::new(),
+                        Some((_, '!')) => {
+                            chars.next();
+                            "!".to_string()
+                        }
+                        Some((_, next)) if next.is_whitespace() || matches!(next, '=' | '(') => {
+                            String::new()
+                        }
+                        Some(_) => {
+                            let mut designator = String::new();
+                            while let Some((_, next)) = chars.peek()
+                                && !next.is_whitespace()
+                                && !matches!(next, ';' | '|' | '&' | '(' | ')' | '<' | '>' | '\'' | '"')
+                            {
+                                designator.push(*next);
+                                chars.next();
+                            }
+                            designator
+                        }
+                    };
+                    if designator.is_empty() {
+                        out.push(c);
+                        continue;
+                    }
+                    match self.event(&designator) {
+                        Some(entry) => out.push_str(entry),
+                        None => return Err(format!("{}: event not found", &line[i..i + 1 + designator.len()])),
+                    }
+                    expanded = true;
+                    continue;
+                }
+                _ => {}
+            }
+            out.push(c);
+        }
+
+        Ok(expanded.then_some(out))
+    }
+
+    /// The entry a `!` designator refers to
+    fn event(&self, designator: &str) -> Option<&str> {
+        if designator == "!" {
+            return self.entries.last().map(String::as_str);
+        }
+        if let Some(back) = designator.strip_prefix('-')
+            && let Ok(back) = back.parse::<usize>()
+        {
+            let index = self.entries.len().checked_sub(back)?;
+            return self.get(index);
+        }
+        if let Ok(number) = designator.parse::<usize>() {
+            return self.get(number.checked_sub(self.first_number)?);
+        }
+        self.entries
+            .iter()
+            .rev()
+            .find(|line| line.starts_with(designator))
+            .map(String::as_str)
+    }
+}
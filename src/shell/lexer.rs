@@ -0,0 +1,411 @@
+//! Tokenizer - splits input into words and operators, honouring quotes and escapes
+
+/// Piece of a word as it was typed
+#[derive(Clone, Debug, PartialEq)]
+pub enum WordPart {
+    /// Literal text. Quoted text is never field-split
+    Literal { text: String, quoted: bool },
+    /// `$NAME`, `${NAME}` or a parameter like `$1`, expanded when the
+    /// command runs
+    Var { name: String, quoted: bool },
+}
+
+/// A shell word, kept unexpanded until execution
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Word {
+    pub parts: Vec<WordPart>,
+}
+
+impl Word {
+    fn push_char(&mut self, c: char, quoted: bool) {
+        if let Some(WordPart::Literal { text, quoted: q }) = self.parts.last_mut()
+            && *q == quoted
+        {
+            text.push(c);
+            return;
+        }
+        self.parts.push(WordPart::Literal { text: c.to_string(), quoted });
+    }
+
+    /// Mark the word as present even if it has no text (e.g. `""`)
+    fn push_empty_quoted(&mut self) {
+        self.parts.push(WordPart::Literal { text: String::new(), quoted: true });
+    }
+
+    /// The text of a word typed without quotes, escapes or variables,
+    /// which is all a reserved word or alias name can be
+    pub fn as_plain(&self) -> Option<&str> {
+        match self.parts.as_slice() {
+            [WordPart::Literal { text, quoted: false }] => Some(text),
+            _ => None,
+        }
+    }
+
+    /// The word as typed, with variables written back as `$NAME`
+    pub fn as_source(&self) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                WordPart::Literal { text, .. } => text.clone(),
+                WordPart::Var { name, .. } => format!("${}", name),
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    Word(Word),
+    Pipe,
+    And,
+    Or,
+    Semi,
+    /// `>` or `>>`, with the descriptor written before it, as in `2>`
+    Redirect { fd: u32, append: bool },
+    /// `>&n`: send a descriptor wherever descriptor `n` goes, as in `2>&1`
+    Duplicate { fd: u32, to: u32 },
+    LParen,
+    RParen,
+    /// Ends a command, like `;`, except inside a compound command where
+    /// lines may follow
+    Newline,
+}
+
+impl Token {
+    pub fn describe(&self) -> String {
+        match self {
+            Token::Word(w) => w.as_source(),
+            Token::Pipe => "|".to_string(),
+            Token::And => "&&".to_string(),
+            Token::Or => "||".to_string(),
+            Token::Semi => ";".to_string(),
+            Token::Redirect { fd, append } => {
+                format!("{}{}", fd_prefix(*fd), if *append { ">>" } else { ">" })
+            }
+            Token::Duplicate { fd, to } => format!("{}>&{}", fd_prefix(*fd), to),
+            Token::LParen => "(".to_string(),
+            Token::RParen => ")".to_string(),
+            Token::Newline => "newline".to_string(),
+        }
+    }
+}
+
+/// Stdout is what `>` redirects when no descriptor is written
+fn fd_prefix(fd: u32) -> String {
+    if fd == 1 { String::new() } else { fd.to_string() }
+}
+
+fn unexpected_eof(expected: char) -> String {
+    format!("rsh: unexpected EOF while looking for matching `{}'", expected)
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// `$?` exit status, `$#` argument count, and `$@` and `$*` all arguments
+fn is_special_param(c: char) -> bool {
+    matches!(c, '?' | '#' | '@' | '*')
+}
+
+/// Read the variable reference after a `$`
+fn read_var(
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+    word: &mut Word,
+    quoted: bool,
+) -> Result<(), String> {
+    match chars.peek() {
+        Some('{') => {
+            chars.next();
+            let mut name = String::new();
+            loop {
+                match chars.next() {
+                    Some('}') => break,
+                    Some(c) => name.push(c),
+                    None => return Err(unexpected_eof('}')),
+                }
+            }
+            let special = name.len() == 1 && name.chars().all(is_special_param);
+            if name.is_empty() || !(special || name.chars().all(is_name_char)) {
+                return Err(format!("rsh: ${{{}}}: bad substitution", name));
+            }
+            word.parts.push(WordPart::Var { name, quoted });
+        }
+        Some(&c) if is_name_start(c) => {
+            let mut name = String::new();
+            while let Some(&c) = chars.peek() {
+                if !is_name_char(c) {
+                    break;
+                }
+                name.push(c);
+                chars.next();
+            }
+            word.parts.push(WordPart::Var { name, quoted });
+        }
+        // `$1`, `$?` and the like are one character long
+        Some(&c) if c.is_ascii_digit() || is_special_param(c) => {
+            chars.next();
+            word.parts.push(WordPart::Var { name: c.to_string(), quoted });
+        }
+        // A lone `$` is just a dollar sign
+        _ => word.push_char('$', quoted),
+    }
+    Ok(())
+}
+
+/// Split input into words and operators
+pub fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut word: Option<Word> = None;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let op = match c {
+            '|' if chars.peek() == Some(&'|') => {
+                chars.next();
+                Some(Token::Or)
+            }
+            '|' => Some(Token::Pipe),
+            '&' if chars.peek() == Some(&'&') => {
+                chars.next();
+                Some(Token::And)
+            }
+            ';' => Some(Token::Semi),
+            '>' => {
+                // A number right before `>` is the descriptor to redirect
+                let fd = word
+                    .as_ref()
+                    .and_then(Word::as_plain)
+                    .filter(|text| text.bytes().all(|b| b.is_ascii_digit()))
+                    .and_then(|digits| digits.parse().ok());
+                if fd.is_some() {
+                    word = None;
+                }
+                let fd = fd.unwrap_or(1);
+                let mut ahead = chars.clone();
+                let to = match (ahead.next(), ahead.next().and_then(|c| c.to_digit(10))) {
+                    (Some('&'), Some(to)) => Some(to),
+                    _ => None,
+                };
+                if let Some(to) = to {
+                    chars.nth(1);
+                    Some(Token::Duplicate { fd, to })
+                } else {
+                    let append = chars.next_if_eq(&'>').is_some();
+                    Some(Token::Redirect { fd, append })
+                }
+            }
+            '(' => Some(Token::LParen),
+            ')' => Some(Token::RParen),
+            '\n' => Some(Token::Newline),
+            // A comment runs to the end of the line
+            '#' if word.is_none() => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+                None
+            }
+            c if c.is_whitespace() => None,
+            '\'' => {
+                let w = word.get_or_insert_with(Word::default);
+                w.push_empty_quoted();
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => w.push_char(c, true),
+                        None => return Err(unexpected_eof('\'')),
+                    }
+                }
+                continue;
+            }
+            '"' => {
+                let w = word.get_or_insert_with(Word::default);
+                w.push_empty_quoted();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.peek() {
+                            Some(&e @ ('$' | '`' | '"' | '\\')) => {
+                                chars.next();
+                                w.push_char(e, true);
+                            }
+                            Some('\n') => {
+                                chars.next();
+                            }
+                            _ => w.push_char('\\', true),
+                        },
+                        Some('$') => read_var(&mut chars, w, true)?,
+                        Some(c) => w.push_char(c, true),
+                        None => return Err(unexpected_eof('"')),
+                    }
+                }
+                continue;
+            }
+            '\\' => {
+                let w = word.get_or_insert_with(Word::default);
+                match chars.next() {
+                    // Line continuation
+                    Some('\n') => {}
+                    Some(e) => w.push_char(e, true),
+                    None => w.push_char('\\', false),
+                }
+                continue;
+            }
+            '$' => {
+                read_var(&mut chars, word.get_or_insert_with(Word::default), false)?;
+                continue;
+            }
+            c => {
+                word.get_or_insert_with(Word::default).push_char(c, false);
+                continue;
+            }
+        };
+
+        if let Some(w) = word.take() {
+            tokens.push(Token::Word(w));
+        }
+        if let Some(op) = op {
+            tokens.push(op);
+        }
+    }
+
+    if let Some(w) = word {
+        tokens.push(Token::Word(w));
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(text: &str, quoted: bool) -> WordPart {
+        WordPart::Literal { text: text.to_string(), quoted }
+    }
+
+    /// The parts of every word, with operators left out
+    fn words(input: &str) -> Vec<Vec<WordPart>> {
+        tokenize(input)
+            .unwrap()
+            .into_iter()
+            .filter_map(|token| match token {
+                Token::Word(word) => Some(word.parts),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn quotes_keep_words_together() {
+        assert_eq!(
+            words("echo 'a  b' \"c d\" e\\ f"),
+            vec![
+                vec![literal("echo", false)],
+                vec![literal("a  b", true)],
+                vec![literal("c d", true)],
+                vec![literal("e", false), literal(" ", true), literal("f", false)],
+            ]
+        );
+        // Adjacent quoted and unquoted text is one word
+        assert_eq!(words("a'b'\"c\"").len(), 1);
+        assert_eq!(words("''"), vec![vec![literal("", true)]]);
+    }
+
+    #[test]
+    fn escapes_inside_and_outside_quotes() {
+        assert_eq!(
+            words(r#""a\"b\\c\$d\e""#),
+            vec![vec![literal(r#"a"b\c$d\e"#, true)]]
+        );
+        // Nothing is special between single quotes
+        assert_eq!(words(r"'a\b$c'"), vec![vec![literal(r"a\b$c", true)]]);
+        assert_eq!(words("a\\\nb"), vec![vec![literal("ab", false)]]);
+        assert_eq!(words(r"\$HOME"), vec![vec![literal("$", true), literal("HOME", false)]]);
+    }
+
+    #[test]
+    fn variables_inside_and_outside_quotes() {
+        let var = |name: &str, quoted| WordPart::Var { name: name.to_string(), quoted };
+        assert_eq!(
+            words(r#"$A "${B}x" $? $"#),
+            vec![
+                vec![var("A", false)],
+                vec![literal("", true), var("B", true), literal("x", true)],
+                vec![var("?", false)],
+                vec![literal("$", false)],
+            ]
+        );
+        assert_eq!(tokenize("${A-b}"), Err("rsh: ${A-b}: bad substitution".to_string()));
+    }
+
+    #[test]
+    fn unterminated_quotes_are_errors() {
+        assert!(tokenize("echo 'a").is_err());
+        assert!(tokenize("echo \"a").is_err());
+        assert!(tokenize("echo ${A").is_err());
+    }
+
+    #[test]
+    fn redirections_in_order() {
+        let word = |text: &str| Token::Word(Word { parts: vec![literal(text, false)] });
+        assert_eq!(
+            tokenize("cmd > out >> log 2> err 2>&1").unwrap(),
+            vec![
+                word("cmd"),
+                Token::Redirect { fd: 1, append: false },
+                word("out"),
+                Token::Redirect { fd: 1, append: true },
+                word("log"),
+                Token::Redirect { fd: 2, append: false },
+                word("err"),
+                Token::Duplicate { fd: 2, to: 1 },
+            ]
+        );
+        assert_eq!(
+            tokenize("cmd 2>&1 >out").unwrap(),
+            vec![
+                word("cmd"),
+                Token::Duplicate { fd: 2, to: 1 },
+                Token::Redirect { fd: 1, append: false },
+                word("out"),
+            ]
+        );
+        // Only a word made of digits names a descriptor
+        assert_eq!(
+            tokenize("echo a2>f").unwrap(),
+            vec![word("echo"), word("a2"), Token::Redirect { fd: 1, append: false }, word("f")]
+        );
+        assert_eq!(
+            tokenize("echo '2'>f").unwrap()[1..3],
+            [
+                Token::Word(Word { parts: vec![literal("2", true)] }),
+                Token::Redirect { fd: 1, append: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn operators_and_comments() {
+        let word = |text: &str| Token::Word(Word { parts: vec![literal(text, false)] });
+        assert_eq!(
+            tokenize("a&&b||c;d|e # ignored\nf").unwrap(),
+            vec![
+                word("a"),
+                Token::And,
+                word("b"),
+                Token::Or,
+                word("c"),
+                Token::Semi,
+                word("d"),
+                Token::Pipe,
+                word("e"),
+                Token::Newline,
+                word("f"),
+            ]
+        );
+        // `#` inside a word is not a comment
+        assert_eq!(tokenize("a#b").unwrap(), vec![word("a#b")]);
+    }
+}
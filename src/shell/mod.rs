@@ -1,24 +1,44 @@
 //! Shell module - handles command input, history, and output
 
+use std::collections::VecDeque;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     layout::Rect,
-    style::{Color, Style},
+    style::Style,
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
 };
 
 use crate::theme::Theme;
+use crate::ui;
 
 mod commands;
+mod complete;
+mod content;
+mod content_walk;
+mod env;
+mod expand;
 mod filesystem;
+mod glob;
+mod history;
+mod lexer;
+mod parser;
+mod prompt;
+mod readline;
+mod time;
 
 use commands::{execute_command, CommandResult};
+use env::{Environment, PROFILE_NAME};
+use filesystem::{child_path, HOME};
+use history::History;
+use readline::{Action, LineEditor};
 pub use filesystem::{FSEntry, VirtualFS};
 
-const PROMPT: &str = "guest@uchindami:~$ ";
-const MAX_HISTORY: usize = 100;
 const MAX_OUTPUT_LINES: usize = 500;
+/// Rows moved per mouse wheel notch
+const WHEEL_LINES: usize = 3;
 
 /// Result of submitting a command
 pub enum ShellResult {
@@ -28,37 +48,108 @@ pub enum ShellResult {
     Exit,
     /// Launch a sub-app
     LaunchApp(&'static str),
+    /// Open the editor on a file
+    Edit {
+        name: String,
+        path: String,
+        content: String,
+    },
+    /// Open the pager
+    Page {
+        title: String,
+        lines: Vec<Line<'static>>,
+    },
 }
 
 /// Shell state
 pub struct Shell {
-    /// Current input buffer
-    input_buffer: String,
-    /// Cursor position in input
-    cursor_pos: usize,
+    /// Line being edited at the prompt
+    editor: LineEditor,
     /// Command history
-    history: Vec<String>,
+    history: History,
     /// Current position in history (for up/down navigation)
     history_pos: Option<usize>,
     /// Output lines (scrollback buffer)
     output: Vec<Line<'static>>,
+    /// Screen rows scrolled back from the bottom of the output
+    scroll: usize,
+    /// Size of the area the shell is drawn in
+    width: usize,
+    height: usize,
     /// Virtual filesystem
     fs: VirtualFS,
     /// Current working directory
     cwd: String,
+    /// Session environment variables
+    env: Environment,
+    /// Candidates being cycled by repeated Tab presses
+    tab_cycle: Option<TabCycle>,
+    /// Ctrl-R search in progress
+    search: Option<HistorySearch>,
+    /// Results still to show after the pager, editor or an app hands the
+    /// screen back
+    pending: VecDeque<CommandResult>,
+}
+
+/// An incremental history search, shown in place of the prompt
+struct HistorySearch {
+    query: String,
+    /// History index of the entry shown
+    found: Option<usize>,
+    /// The last key found no match
+    failed: bool,
+}
+
+/// Completion candidates offered for an ambiguous word
+struct TabCycle {
+    /// Byte offset of the word in the input
+    start: usize,
+    candidates: Vec<String>,
+    /// Candidate the next Tab inserts
+    next: usize,
 }
 
 impl Shell {
-    pub fn new() -> Self {
-        Self {
-            input_buffer: String::new(),
-            cursor_pos: 0,
-            history: Vec::new(),
+    pub fn new(fs: VirtualFS) -> Self {
+        let mut shell = Self {
+            editor: LineEditor::new(),
+            history: History::new(),
             history_pos: None,
             output: Vec::new(),
-            fs: VirtualFS::new(),
-            cwd: "~".to_string(),
+            scroll: 0,
+            width: 0,
+            height: 0,
+            fs,
+            cwd: HOME.to_string(),
+            env: Environment::new(),
+            tab_cycle: None,
+            search: None,
+            pending: VecDeque::new(),
+        };
+        shell.source_profile();
+        shell
+    }
+
+    /// Run `~/.profile` like a login shell, keeping anything it prints
+    fn source_profile(&mut self) {
+        let profile = child_path(HOME, PROFILE_NAME);
+        if self.fs.get(&profile).is_none() {
+            return;
+        }
+        let results = execute_command(
+            &format!("source {}", profile),
+            &mut self.fs,
+            &mut self.cwd,
+            &mut self.env,
+            &mut self.history,
+        );
+        for result in results.into_iter().flat_map(CommandResult::flatten) {
+            if let CommandResult::Output { stdout, stderr, .. } = result {
+                self.output.extend(stdout);
+                self.output.extend(stderr);
+            }
         }
+        self.editor.set_vi(self.env.option("vi"));
     }
 
     /// Display welcome message
@@ -89,6 +180,7 @@ impl Shell {
             "  Complete the sentence: I use ____ btw",
             "",
             "Type 'ls' to see available commands, or 'help' for assistance.",
+            "New here? Run '~/bin/tour.sh' for a guided tour.",
             "",
         ];
 
@@ -102,37 +194,153 @@ impl Shell {
     
 
 
-    /// Handle character input
-    pub fn input(&mut self, c: char) {
-        self.input_buffer.insert(self.cursor_pos, c);
-        self.cursor_pos += 1;
-        self.history_pos = None;
+    /// Track the size of the output area. The width is exported as `$COLUMNS`.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.env.set("COLUMNS", &width.to_string());
+        self.width = width as usize;
+        self.height = height as usize;
     }
 
-    /// Handle backspace
-    pub fn backspace(&mut self) {
-        if self.cursor_pos > 0 {
-            self.cursor_pos -= 1;
-            self.input_buffer.remove(self.cursor_pos);
+    /// Handle a key press at the prompt
+    pub fn handle_key(&mut self, key: KeyEvent) -> ShellResult {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::PageUp => self.scroll_up(self.page()),
+            KeyCode::PageDown => self.scroll_down(self.page()),
+            KeyCode::Up if shift => self.scroll_up(1),
+            KeyCode::Down if shift => self.scroll_down(1),
+            _ => {
+                // Anything else snaps back to the prompt
+                self.scroll = 0;
+                let result = if self.search.is_some() {
+                    self.search_key(key)
+                } else {
+                    self.edit_key(key)
+                };
+                self.trim_output();
+                return result;
+            }
         }
+        ShellResult::None
+    }
+
+    /// Mouse wheel up
+    pub fn wheel_up(&mut self) {
+        self.scroll_up(WHEEL_LINES);
+    }
+
+    /// Mouse wheel down
+    pub fn wheel_down(&mut self) {
+        self.scroll_down(WHEEL_LINES);
     }
 
-    /// Move cursor left
-    pub fn cursor_left(&mut self) {
-        if self.cursor_pos > 0 {
-            self.cursor_pos -= 1;
+    /// Output rows on screen while scrolled back; the rest go to the
+    /// input line and the "more" indicator
+    fn page(&self) -> usize {
+        let input_rows = ui::wrap_line(&self.input_line(), self.width).len();
+        self.height.saturating_sub(input_rows + 1).max(1)
+    }
+
+    /// Scroll back by `rows` screen rows
+    fn scroll_up(&mut self, rows: usize) {
+        let max = self.output_rows(self.width).len().saturating_sub(self.page());
+        self.scroll = (self.scroll + rows).min(max);
+    }
+
+    fn scroll_down(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_sub(rows);
+    }
+
+    /// Drop the oldest output beyond the scrollback limit
+    fn trim_output(&mut self) {
+        let excess = self.output.len().saturating_sub(MAX_OUTPUT_LINES);
+        self.output.drain(..excess);
+    }
+
+    /// A key for the line editor
+    fn edit_key(&mut self, key: KeyEvent) -> ShellResult {
+        let action = self.editor.handle_key(key);
+        if action != Action::Complete {
+            self.tab_cycle = None;
         }
+        match action {
+            Action::None => {}
+            Action::Edited => self.history_pos = None,
+            Action::Submit => return self.submit(),
+            Action::Complete => self.tab_complete(),
+            Action::HistoryPrev => self.history_prev(),
+            Action::HistoryNext => self.history_next(),
+            Action::SearchHistory => {
+                self.search = Some(HistorySearch {
+                    query: String::new(),
+                    found: None,
+                    failed: false,
+                });
+            }
+            Action::ClearScreen => self.output.clear(),
+            Action::EndOfFile => return ShellResult::Exit,
+        }
+        ShellResult::None
     }
 
-    /// Move cursor right
-    pub fn cursor_right(&mut self) {
-        if self.cursor_pos < self.input_buffer.len() {
-            self.cursor_pos += 1;
+    /// Keys during a Ctrl-R search. Typing narrows the search, Ctrl-R finds
+    /// the next older match, Ctrl-G gives up, and any other key takes the
+    /// match and then acts as usual.
+    fn search_key(&mut self, key: KeyEvent) -> ShellResult {
+        let Some(search) = &mut self.search else {
+            return ShellResult::None;
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        // Where to start looking: older than the current match, at it, or from the newest entry
+        let before = match key.code {
+            KeyCode::Char('r') if ctrl => search.found.unwrap_or(self.history.len()),
+            KeyCode::Char('g') if ctrl => {
+                self.search = None;
+                return ShellResult::None;
+            }
+            KeyCode::Esc => {
+                self.accept_search();
+                return ShellResult::None;
+            }
+            KeyCode::Char(c) if !ctrl => {
+                search.query.push(c);
+                search.found.map_or(self.history.len(), |i| i + 1)
+            }
+            KeyCode::Backspace => {
+                search.query.pop();
+                self.history.len()
+            }
+            _ => {
+                self.accept_search();
+                return self.handle_key(key);
+            }
+        };
+
+        if search.query.is_empty() {
+            search.found = None;
+            search.failed = false;
+        } else if let Some(i) = self.history.search(&search.query, before) {
+            search.found = Some(i);
+            search.failed = false;
+        } else {
+            search.failed = true;
+        }
+        ShellResult::None
+    }
+
+    /// End the search, putting the match on the prompt
+    fn accept_search(&mut self) {
+        if let Some(HistorySearch { found: Some(i), .. }) = self.search.take()
+            && let Some(entry) = self.history.get(i)
+        {
+            self.editor.set_text(entry);
+            self.history_pos = Some(i);
         }
     }
 
     /// Navigate to previous history entry
-    pub fn history_prev(&mut self) {
+    fn history_prev(&mut self) {
         if self.history.is_empty() {
             return;
         }
@@ -142,43 +350,75 @@ impl Shell {
             Some(p) => p - 1,
         };
         self.history_pos = Some(new_pos);
-        self.input_buffer = self.history[new_pos].clone();
-        self.cursor_pos = self.input_buffer.len();
+        self.editor.set_text(self.history.get(new_pos).unwrap_or_default());
     }
 
     /// Navigate to next history entry
-    pub fn history_next(&mut self) {
+    fn history_next(&mut self) {
         match self.history_pos {
             None => {}
             Some(p) if p >= self.history.len() - 1 => {
                 self.history_pos = None;
-                self.input_buffer.clear();
-                self.cursor_pos = 0;
+                self.editor.set_text("");
             }
             Some(p) => {
                 self.history_pos = Some(p + 1);
-                self.input_buffer = self.history[p + 1].clone();
-                self.cursor_pos = self.input_buffer.len();
+                self.editor.set_text(self.history.get(p + 1).unwrap_or_default());
             }
         }
     }
 
-    /// Tab completion
-    pub fn tab_complete(&mut self) {
-        let completions = self.fs.complete(&self.input_buffer, &self.cwd);
-        if completions.len() == 1 {
-            self.input_buffer = completions[0].clone();
-            self.cursor_pos = self.input_buffer.len();
-        } else if completions.len() > 1 {
-            // Show available completions
-            self.output.push(Line::from(format!("{}{}", PROMPT, self.input_buffer)));
-            self.output.push(Line::from(completions.join("  ")));
+    /// Tab completion. A unique match is inserted outright; otherwise the
+    /// longest common prefix is inserted and the candidates listed, and
+    /// further presses cycle through them.
+    fn tab_complete(&mut self) {
+        if let Some(cycle) = &mut self.tab_cycle {
+            let candidate = cycle.candidates[cycle.next].clone();
+            cycle.next = (cycle.next + 1) % cycle.candidates.len();
+            let start = cycle.start;
+            self.replace_word(start, &candidate);
+            return;
+        }
+
+        let before = &self.editor.text()[..self.editor.cursor()];
+        let completion = complete::complete(before, &self.fs, &self.cwd);
+        let word_len = self.editor.cursor() - completion.start;
+
+        match completion.candidates.as_slice() {
+            [] => {}
+            [only] => {
+                // Finished words get a space; directories stay open for more
+                let text = if only.ends_with('/') { only.clone() } else { format!("{} ", only) };
+                self.replace_word(completion.start, &text);
+            }
+            candidates => {
+                let prefix = complete::common_prefix(candidates);
+                if prefix.len() > word_len {
+                    self.replace_word(completion.start, &prefix);
+                }
+                let names: Vec<&str> = candidates
+                    .iter()
+                    .map(|c| c.trim_end_matches('/').rsplit('/').next().unwrap_or(c))
+                    .collect();
+                self.output.push(Line::from(format!("{}{}", self.get_prompt(), self.editor.text())));
+                self.output.push(Line::from(names.join("  ")));
+                self.tab_cycle = Some(TabCycle {
+                    start: completion.start,
+                    candidates: completion.candidates,
+                    next: 0,
+                });
+            }
         }
     }
 
+    /// Replace the input from byte `start` up to the cursor
+    fn replace_word(&mut self, start: usize, text: &str) {
+        self.editor.replace_before_cursor(start, text);
+    }
+
     /// Submit current input and execute command
-    pub fn submit(&mut self) -> ShellResult {
-        let cmd = self.input_buffer.trim().to_string();
+    fn submit(&mut self) -> ShellResult {
+        let mut cmd = self.editor.take().trim().to_string();
 
         // Add prompt + input to output
         self.output.push(Line::from(vec![
@@ -186,97 +426,169 @@ impl Shell {
             Span::raw(cmd.clone()),
         ]));
 
-        // Clear input
-        self.input_buffer.clear();
-        self.cursor_pos = 0;
-
         if cmd.is_empty() {
             return ShellResult::None;
         }
 
-        // Check for exit commands first
-        if cmd == "exit" || cmd == "quit" {
-            return ShellResult::Exit;
+        // Expand `!` references, echoing the result like bash does
+        match self.history.expand(&cmd) {
+            Ok(Some(expanded)) => {
+                self.output.push(Line::from(expanded.clone()));
+                cmd = expanded;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.output.push(ui::error_text(&format!("rsh: {}", e)));
+                return ShellResult::None;
+            }
         }
 
         // Add to history
-        if self.history.last() != Some(&cmd) {
-            self.history.push(cmd.clone());
-            if self.history.len() > MAX_HISTORY {
-                self.history.remove(0);
-            }
-        }
+        self.history.push(&cmd);
         self.history_pos = None;
 
         // Execute command
-        let result = execute_command(&cmd, &mut self.fs, &mut self.cwd);
-        match result {
-            CommandResult::Output(lines) => {
-                for line in lines {
-                    self.output.push(line);
+        let results = execute_command(
+            &cmd,
+            &mut self.fs,
+            &mut self.cwd,
+            &mut self.env,
+            &mut self.history,
+        );
+        // `set -o vi` / `set -o emacs` apply from the next line on
+        self.editor.set_vi(self.env.option("vi"));
+        self.pending = results.into_iter().flat_map(CommandResult::flatten).collect();
+        self.resume()
+    }
+
+    /// Show the results of the last command line up to the next one that
+    /// takes over the screen. Called again when the screen is handed back.
+    pub fn resume(&mut self) -> ShellResult {
+        while let Some(result) = self.pending.pop_front() {
+            match result {
+                CommandResult::Output { stdout, stderr, .. } => {
+                    self.output.extend(stdout);
+                    self.output.extend(stderr);
                 }
-                ShellResult::None
-            }
-            CommandResult::Clear => {
-                self.output.clear();
-                ShellResult::None
-            }
-            CommandResult::AppLaunch(app_name) => {
-                ShellResult::LaunchApp(app_name)
+                CommandResult::Clear => {
+                    self.output.clear();
+                }
+                CommandResult::Exit(_) => {
+                    return ShellResult::Exit;
+                }
+                CommandResult::AppLaunch(app_name) => {
+                    return ShellResult::LaunchApp(app_name);
+                }
+                CommandResult::Edit { name, path, content } => {
+                    return ShellResult::Edit { name, path, content };
+                }
+                CommandResult::Page { title, lines } => {
+                    return ShellResult::Page { title, lines };
+                }
+                CommandResult::Sequence(_) => unreachable!("sequences are flattened"),
             }
         }
+        ShellResult::None
     }
 
-    /// Get current prompt string
+    /// Save a file written by the editor
+    pub fn save_file(&mut self, path: &str, content: &str) -> Result<(), String> {
+        self.fs.write_file(path, content, false)
+    }
+
+    /// Get current prompt string, from `$PS1`
     fn get_prompt(&self) -> String {
-        format!("guest@uchindami:{}$ ", self.cwd)
+        prompt::text(&self.env, &self.cwd)
     }
 
     /// Render shell to frame
     pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let width = area.width as usize;
         let height = area.height as usize;
 
-        // Calculate visible lines (reserve 1 for input line)
-        let visible_height = height.saturating_sub(1);
+        // The input line wraps too; if it outgrows the screen, keep its end
+        let mut input = ui::wrap_line(&self.input_line(), width);
+        input.drain(..input.len().saturating_sub(height));
+        let visible_height = height - input.len();
 
         // Build output lines
         let mut lines: Vec<Line> = Vec::new();
 
-        // Add output history (show last N lines)
-        let start = self.output.len().saturating_sub(visible_height);
-        for line in &self.output[start..] {
-            lines.push(line.clone());
+        // Add output history: the last N rows, or an earlier window when
+        // scrolled back, with a line saying how much is below
+        let rows = self.output_rows(width);
+        let page = visible_height.saturating_sub(1);
+        let scroll = self.scroll.min(rows.len().saturating_sub(page));
+        if scroll == 0 {
+            let start = rows.len().saturating_sub(visible_height);
+            lines.extend(rows.into_iter().skip(start));
+        } else {
+            let end = rows.len() - scroll;
+            let start = end.saturating_sub(page);
+            lines.extend(rows.into_iter().take(end).skip(start));
+            lines.push(Line::from(Span::styled(
+                format!("-- more ({} below) --", scroll),
+                Style::default().fg(Theme::WARNING),
+            )));
         }
 
-        // Add current input line with cursor - multi-color prompt
-        let before_cursor: String = self.input_buffer.chars().take(self.cursor_pos).collect();
-        let cursor_char = self.input_buffer.chars().nth(self.cursor_pos).unwrap_or(' ');
-        let after_cursor: String = self.input_buffer.chars().skip(self.cursor_pos + 1).collect();
-
-        lines.push(Line::from(vec![
-            // User: green
-            Span::styled("guest", Style::default().fg(Theme::GUEST)),
-            // @ symbol: muted
-            Span::styled("@", Style::default().fg(Theme::SECONDARY)),
-            // Host: primary color
-            Span::styled("uchindami", Style::default().fg(Theme::SECONDARY)),
-            // Colon: muted
-            Span::styled(":", Style::default().fg(Theme::MUTED)),
-            // Path: secondary/coral
-            Span::styled(&self.cwd, Style::default().fg(Theme::SECONDARY)),
-            // Dollar sign: warning/gold
-            Span::styled("$ ", Style::default().fg(Theme::SECONDARY)),
+        lines.extend(input);
+        let paragraph = Paragraph::new(lines);
+        frame.render_widget(paragraph, area);
+    }
+
+    /// Output wrapped to `width`, one line per screen row
+    fn output_rows(&self, width: usize) -> Vec<Line<'static>> {
+        self.output
+            .iter()
+            .flat_map(|line| ui::wrap_line(line, width))
+            .collect()
+    }
+
+    /// Current input line with cursor - multi-color prompt
+    fn input_line(&self) -> Line<'static> {
+        if let Some(search) = &self.search {
+            return self.search_line(search);
+        }
+
+        let (before_cursor, cursor_char, after_cursor) = self.editor.split_at_cursor();
+        let cursor_char = if cursor_char.is_empty() { " " } else { cursor_char };
+        // vi command mode gets a differently coloured block
+        let cursor_bg = if self.editor.in_normal_mode() { Theme::WARNING } else { Theme::SUCCESS };
+
+        let mut spans = prompt::spans(&self.env, &self.cwd);
+        spans.extend([
             // User input
-            Span::raw(before_cursor),
+            Span::raw(before_cursor.to_string()),
             // Cursor
             Span::styled(
                 cursor_char.to_string(),
-                Style::default().bg(Theme::SUCCESS).fg(Theme::BACKGROUND),
+                Style::default().bg(cursor_bg).fg(Theme::BACKGROUND),
             ),
-            Span::raw(after_cursor),
-        ]));
+            Span::raw(after_cursor.to_string()),
+        ]);
+        Line::from(spans)
+    }
 
-        let paragraph = Paragraph::new(lines);
-        frame.render_widget(paragraph, area);
+    /// The prompt line during a Ctrl-R search, with the cursor on the match
+    fn search_line(&self, search: &HistorySearch) -> Line<'static> {
+        let label = if search.failed { "(failed reverse-i-search)" } else { "(reverse-i-search)" };
+        let entry = search.found.and_then(|i| self.history.get(i)).unwrap_or_default();
+        let at = entry.find(search.query.as_str()).unwrap_or(entry.len());
+        let cursor_len = entry[at..].chars().next().map_or(0, char::len_utf8);
+        let cursor_char = if cursor_len == 0 { " " } else { &entry[at..at + cursor_len] };
+
+        Line::from(vec![
+            Span::styled(
+                format!("{}`{}': ", label, search.query),
+                Style::default().fg(Theme::MUTED),
+            ),
+            Span::raw(entry[..at].to_string()),
+            Span::styled(
+                cursor_char.to_string(),
+                Style::default().bg(Theme::SUCCESS).fg(Theme::BACKGROUND),
+            ),
+            Span::raw(entry[at + cursor_len..].to_string()),
+        ])
     }
 }
@@ -0,0 +1,666 @@
+//! Command parser - turns command lines and scripts into lists of pipelines
+
+use super::env::{is_valid_name, Environment};
+use super::lexer::{self, Token, Word};
+
+/// How a pipeline is joined to the one before it
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Connector {
+    /// `;` - always run
+    Seq,
+    /// `&&` - run if the previous pipeline succeeded
+    And,
+    /// `||` - run if the previous pipeline failed
+    Or,
+}
+
+/// Output redirection attached to a command
+#[derive(Clone, Debug)]
+pub struct Redirect {
+    /// Descriptor redirected: 1 for stdout, 2 for stderr
+    pub fd: u32,
+    pub target: RedirectTarget,
+}
+
+/// Where a redirection sends its descriptor
+#[derive(Clone, Debug)]
+pub enum RedirectTarget {
+    /// A file path; `>>` appends instead of truncating
+    File { path: Word, append: bool },
+    /// Wherever another descriptor goes, as in `2>&1`
+    Fd(u32),
+}
+
+/// A single command with its arguments and redirections
+#[derive(Clone, Debug, Default)]
+pub struct SimpleCommand {
+    pub words: Vec<Word>,
+    pub redirects: Vec<Redirect>,
+}
+
+impl SimpleCommand {
+    /// True when there is neither a command word nor a redirection
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty() && self.redirects.is_empty()
+    }
+}
+
+/// One stage of a pipeline
+#[derive(Clone, Debug)]
+pub enum Command {
+    Simple(SimpleCommand),
+    /// `name() { list; }` - defines a function instead of running anything
+    Function { name: String, body: CommandList },
+    /// `{ list; }`
+    Group(CommandList),
+    /// `if list; then list; [elif list; then list;]... [else list;] fi`
+    If {
+        /// Conditions and the lists they guard, tried in order
+        branches: Vec<(CommandList, CommandList)>,
+        otherwise: Option<CommandList>,
+    },
+    /// `for name [in word...]; do list; done`
+    For {
+        var: String,
+        /// `None` loops over the positional parameters
+        words: Option<Vec<Word>>,
+        body: CommandList,
+    },
+    /// `while list; do list; done`, or `until` when `until` is set
+    While {
+        condition: CommandList,
+        body: CommandList,
+        until: bool,
+    },
+    /// A compound command with redirections after it, which apply to
+    /// everything it runs, as in `for ...; done > file`
+    Redirected {
+        command: Box<Command>,
+        redirects: Vec<Redirect>,
+    },
+}
+
+impl Command {
+    /// The redirections written after the command
+    pub fn redirects(&self) -> &[Redirect] {
+        match self {
+            Command::Simple(simple) => &simple.redirects,
+            Command::Redirected { redirects, .. } => redirects,
+            _ => &[],
+        }
+    }
+}
+
+/// Commands connected with `|`
+#[derive(Clone, Debug, Default)]
+pub struct Pipeline {
+    pub commands: Vec<Command>,
+}
+
+/// Pipelines joined by `;`, `&&`, `||` and, inside compound commands,
+/// newlines
+#[derive(Clone, Debug, Default)]
+pub struct CommandList {
+    /// The first entry's connector is always `Seq`
+    pub items: Vec<(Connector, Pipeline)>,
+}
+
+/// Words that start or continue a compound command
+const RESERVED: &[&str] = &[
+    "if", "then", "elif", "else", "fi", "for", "do", "done", "while", "until", "{", "}",
+];
+
+fn syntax_error(token: Option<&Token>) -> String {
+    let near = token.map(Token::describe).unwrap_or_else(|| "newline".to_string());
+    format!("rsh: syntax error near unexpected token `{}'", near)
+}
+
+/// Input parsed one complete command at a time: a line, or several when
+/// a compound command spans them. Each command is parsed just before it
+/// runs, so aliases defined by one apply to the next.
+pub struct Script {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Aliases being expanded, with the end of their text in `tokens`.
+    /// An alias is not expanded again inside its own text.
+    active: Vec<(String, usize)>,
+}
+
+impl Script {
+    pub fn new(input: &str) -> Result<Self, String> {
+        Ok(Self {
+            tokens: lexer::tokenize(input)?,
+            pos: 0,
+            active: Vec::new(),
+        })
+    }
+
+    /// The next complete command, expanding the session's aliases, or
+    /// `None` at the end of the input
+    pub fn next_command(&mut self, env: &Environment) -> Option<Result<CommandList, String>> {
+        let mut parser = Parser { script: self, env };
+        parser.skip_newlines();
+        parser.peek()?;
+        Some(parser.complete_command())
+    }
+}
+
+/// Recursive descent over a script's tokens
+struct Parser<'a> {
+    script: &'a mut Script,
+    /// Where aliases are looked up
+    env: &'a Environment,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.script.tokens.get(self.script.pos)
+    }
+
+    fn advance(&mut self) {
+        self.script.pos += 1;
+    }
+
+    fn skip_newlines(&mut self) {
+        while self.peek() == Some(&Token::Newline) {
+            self.advance();
+        }
+    }
+
+    /// Whether the next token is the reserved word `word`
+    fn at_reserved(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w.as_plain() == Some(word))
+    }
+
+    /// Consume the reserved word `word`, which must be next
+    fn expect(&mut self, word: &str) -> Result<(), String> {
+        if !self.at_reserved(word) {
+            return Err(syntax_error(self.peek()));
+        }
+        self.advance();
+        Ok(())
+    }
+
+    /// A top-level list and the newline ending it
+    fn complete_command(&mut self) -> Result<CommandList, String> {
+        let list = self.list(&[])?;
+        match self.peek() {
+            None => Ok(list),
+            Some(Token::Newline) => {
+                self.advance();
+                Ok(list)
+            }
+            token => Err(syntax_error(token)),
+        }
+    }
+
+    /// Pipelines joined by `;`, `&&` and `||`, up to one of the reserved
+    /// words in `ends` where a command would start. With no `ends` this is
+    /// a top-level list, which also stops at a newline.
+    fn list(&mut self, ends: &[&str]) -> Result<CommandList, String> {
+        let top = ends.is_empty();
+        let mut list = CommandList::default();
+        let mut connector = Connector::Seq;
+        loop {
+            if !top {
+                self.skip_newlines();
+            }
+            let end = match self.peek() {
+                None | Some(Token::Newline) => true,
+                Some(_) => ends.iter().any(|end| self.at_reserved(end)),
+            };
+            if end {
+                // A list may end after `;` but not after `&&` or `||`
+                if connector != Connector::Seq {
+                    return Err(syntax_error(self.peek()));
+                }
+                return Ok(list);
+            }
+            list.items.push((connector, self.pipeline()?));
+            connector = match self.peek() {
+                Some(Token::And) => Connector::And,
+                Some(Token::Or) => Connector::Or,
+                Some(Token::Semi) => Connector::Seq,
+                Some(Token::Newline) if !top => Connector::Seq,
+                _ => return Ok(list),
+            };
+            self.advance();
+            // A line may end after `&&` or `||`
+            if connector != Connector::Seq {
+                self.skip_newlines();
+            }
+        }
+    }
+
+    /// A list that must have at least one command, as in a compound command
+    fn body(&mut self, ends: &[&str]) -> Result<CommandList, String> {
+        let list = self.list(ends)?;
+        if list.items.is_empty() {
+            return Err(syntax_error(self.peek()));
+        }
+        Ok(list)
+    }
+
+    fn pipeline(&mut self) -> Result<Pipeline, String> {
+        let mut pipeline = Pipeline {
+            commands: vec![self.command()?],
+        };
+        while self.peek() == Some(&Token::Pipe) {
+            self.advance();
+            self.skip_newlines();
+            pipeline.commands.push(self.command()?);
+        }
+        Ok(pipeline)
+    }
+
+    fn command(&mut self) -> Result<Command, String> {
+        self.expand_alias()?;
+        let reserved = match self.peek() {
+            Some(Token::Word(word)) => word.as_plain().filter(|word| RESERVED.contains(word)),
+            _ => None,
+        };
+        let compound = match reserved {
+            Some("if") => self.if_clause()?,
+            Some("for") => self.for_clause()?,
+            Some(word @ ("while" | "until")) => {
+                let until = word == "until";
+                self.advance();
+                let condition = self.body(&["do"])?;
+                self.expect("do")?;
+                let body = self.body(&["done"])?;
+                self.expect("done")?;
+                Command::While { condition, body, until }
+            }
+            Some("{") => Command::Group(self.group()?),
+            Some(_) => return Err(syntax_error(self.peek())),
+            None => return self.simple_command(),
+        };
+
+        let mut redirects = Vec::new();
+        while let Some(redirect) = self.redirect()? {
+            redirects.push(redirect);
+        }
+        if redirects.is_empty() {
+            return Ok(compound);
+        }
+        Ok(Command::Redirected {
+            command: Box::new(compound),
+            redirects,
+        })
+    }
+
+    /// A function definition, or words and redirections
+    fn simple_command(&mut self) -> Result<Command, String> {
+        if let Some(name) = self.function_name() {
+            self.script.pos += 3;
+            self.skip_newlines();
+            let body = self.group()?;
+            return Ok(Command::Function { name, body });
+        }
+
+        let mut command = SimpleCommand::default();
+        loop {
+            if let Some(Token::Word(w)) = self.peek() {
+                command.words.push(w.clone());
+                self.advance();
+            } else if let Some(redirect) = self.redirect()? {
+                command.redirects.push(redirect);
+            } else {
+                break;
+            }
+        }
+        if command.is_empty() {
+            return Err(syntax_error(self.peek()));
+        }
+        Ok(Command::Simple(command))
+    }
+
+    /// The redirection starting at the next token, if there is one
+    fn redirect(&mut self) -> Result<Option<Redirect>, String> {
+        match self.peek() {
+            Some(&Token::Redirect { fd, append }) => {
+                self.advance();
+                let Some(Token::Word(path)) = self.peek() else {
+                    return Err(syntax_error(self.peek()));
+                };
+                let target = RedirectTarget::File {
+                    path: path.clone(),
+                    append,
+                };
+                self.advance();
+                Ok(Some(Redirect { fd, target }))
+            }
+            Some(&Token::Duplicate { fd, to }) => {
+                self.advance();
+                Ok(Some(Redirect {
+                    fd,
+                    target: RedirectTarget::Fd(to),
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// `{ list; }`
+    fn group(&mut self) -> Result<CommandList, String> {
+        self.expect("{")?;
+        let body = self.body(&["}"])?;
+        self.expect("}")?;
+        Ok(body)
+    }
+
+    fn if_clause(&mut self) -> Result<Command, String> {
+        let mut branches = Vec::new();
+        let mut otherwise = None;
+        // `if` the first time round, `elif` after that
+        self.advance();
+        loop {
+            let condition = self.body(&["then"])?;
+            self.expect("then")?;
+            let body = self.body(&["elif", "else", "fi"])?;
+            branches.push((condition, body));
+            if self.at_reserved("elif") {
+                self.advance();
+                continue;
+            }
+            if self.at_reserved("else") {
+                self.advance();
+                otherwise = Some(self.body(&["fi"])?);
+            }
+            self.expect("fi")?;
+            return Ok(Command::If { branches, otherwise });
+        }
+    }
+
+    fn for_clause(&mut self) -> Result<Command, String> {
+        self.advance();
+        let var = match self.peek() {
+            Some(Token::Word(word)) => word.as_plain().filter(|name| is_valid_name(name)),
+            _ => None,
+        };
+        let Some(var) = var.map(str::to_string) else {
+            return Err(syntax_error(self.peek()));
+        };
+        self.advance();
+        self.skip_newlines();
+
+        let mut words = None;
+        if self.at_reserved("in") {
+            self.advance();
+            let mut list = Vec::new();
+            while let Some(Token::Word(word)) = self.peek() {
+                list.push(word.clone());
+                self.advance();
+            }
+            words = Some(list);
+        }
+        if matches!(self.peek(), Some(Token::Semi | Token::Newline)) {
+            self.advance();
+        }
+        self.skip_newlines();
+        self.expect("do")?;
+        let body = self.body(&["done"])?;
+        self.expect("done")?;
+        Ok(Command::For { var, words, body })
+    }
+
+    /// The name in `name ( )`, which starts a function definition
+    fn function_name(&self) -> Option<String> {
+        let pos = self.script.pos;
+        match self.script.tokens.get(pos..pos + 3)? {
+            [Token::Word(word), Token::LParen, Token::RParen] => word
+                .as_plain()
+                .filter(|name| is_valid_name(name))
+                .map(str::to_string),
+            _ => None,
+        }
+    }
+
+    /// Replace an alias where a command starts with the tokens of its text,
+    /// as many times as the result starts with another alias
+    fn expand_alias(&mut self) -> Result<(), String> {
+        let env = self.env;
+        let script = &mut *self.script;
+        let pos = script.pos;
+        loop {
+            let Some(Token::Word(word)) = script.tokens.get(pos) else {
+                return Ok(());
+            };
+            let Some(name) = word.as_plain() else {
+                return Ok(());
+            };
+            if script.active.iter().any(|(active, end)| active == name && pos < *end) {
+                return Ok(());
+            }
+            let Some(value) = env.alias(name) else {
+                return Ok(());
+            };
+
+            let name = name.to_string();
+            let tokens = lexer::tokenize(value)?;
+            let added = tokens.len();
+            for (_, end) in &mut script.active {
+                if *end > pos {
+                    *end = *end + added - 1;
+                }
+            }
+            script.active.push((name, pos + added));
+            script.tokens.splice(pos..=pos, tokens);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every command in `input`, with `aliases` defined
+    fn parse_with(input: &str, aliases: &[(&str, &str)]) -> Result<Vec<CommandList>, String> {
+        let mut env = Environment::new();
+        for (name, value) in aliases {
+            env.set_alias(name, value);
+        }
+        let mut script = Script::new(input)?;
+        let mut lists = Vec::new();
+        while let Some(list) = script.next_command(&env) {
+            lists.push(list?);
+        }
+        Ok(lists)
+    }
+
+    /// The one command line in `input`
+    fn parse(input: &str) -> CommandList {
+        let mut lists = parse_with(input, &[]).unwrap();
+        assert_eq!(lists.len(), 1);
+        lists.remove(0)
+    }
+
+    fn simple(command: &Command) -> &SimpleCommand {
+        match command {
+            Command::Simple(simple) => simple,
+            other => panic!("not a simple command: {:?}", other),
+        }
+    }
+
+    fn words(command: &Command) -> Vec<String> {
+        simple(command).words.iter().map(Word::as_source).collect()
+    }
+
+    /// The only command of the only pipeline in a list
+    fn only(list: &CommandList) -> &Command {
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].1.commands.len(), 1);
+        &list.items[0].1.commands[0]
+    }
+
+    /// Redirections written back as text, in order
+    fn redirects(command: &Command) -> Vec<String> {
+        simple(command)
+            .redirects
+            .iter()
+            .map(|redirect| match &redirect.target {
+                RedirectTarget::File { path, append } => {
+                    format!("{}{}{}", redirect.fd, if *append { ">>" } else { ">" }, path.as_source())
+                }
+                RedirectTarget::Fd(to) => format!("{}>&{}", redirect.fd, to),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn redirections_keep_their_order() {
+        let list = parse("cmd > out 2>&1");
+        assert_eq!(redirects(only(&list)), ["1>out", "2>&1"]);
+        let list = parse("cmd 2>&1 >> log a");
+        assert_eq!(redirects(only(&list)), ["2>&1", "1>>log"]);
+        assert_eq!(words(only(&list)), ["cmd", "a"]);
+        // Redirections alone make a command
+        assert_eq!(redirects(only(&parse("> f"))), ["1>f"]);
+        assert!(parse_with("cmd >", &[]).is_err());
+        assert!(parse_with("cmd > | b", &[]).is_err());
+    }
+
+    #[test]
+    fn redirections_after_compound_commands() {
+        let list = parse("for x in a; do echo $x; done > out 2>&1 | wc");
+        let command = &list.items[0].1.commands[0];
+        let Command::Redirected { command: inner, redirects } = command else {
+            panic!("not redirected");
+        };
+        assert!(matches!(**inner, Command::For { .. }));
+        assert_eq!(redirects.len(), 2);
+        let fds: Vec<u32> = command.redirects().iter().map(|redirect| redirect.fd).collect();
+        assert_eq!(fds, [1, 2]);
+        assert!(matches!(only(&parse("{ a; } >> log")), Command::Redirected { .. }));
+        assert!(matches!(only(&parse("{ a; }")), Command::Group(_)));
+        // Words cannot follow the redirections
+        assert!(parse_with("{ a; } > f b", &[]).is_err());
+    }
+
+    #[test]
+    fn list_connectors() {
+        let list = parse("a | b && c || d; e");
+        let connectors: Vec<Connector> = list.items.iter().map(|(connector, _)| *connector).collect();
+        assert_eq!(connectors, [Connector::Seq, Connector::And, Connector::Or, Connector::Seq]);
+        // `|` binds tighter than `&&`, `||` and `;`
+        assert_eq!(list.items[0].1.commands.len(), 2);
+        assert_eq!(words(&list.items[0].1.commands[1]), ["b"]);
+        assert_eq!(list.items[1].1.commands.len(), 1);
+        // A trailing `;` is allowed, a trailing `&&` is not
+        assert_eq!(parse("a;").items.len(), 1);
+        assert_eq!(
+            parse_with("a &&", &[]).unwrap_err(),
+            "rsh: syntax error near unexpected token `newline'"
+        );
+        assert_eq!(
+            parse_with("a ;; b", &[]).unwrap_err(),
+            "rsh: syntax error near unexpected token `;'"
+        );
+        assert!(parse_with("| a", &[]).is_err());
+    }
+
+    #[test]
+    fn lines_are_separate_commands() {
+        let lists = parse_with("a\n\nb && \\\nc\n", &[]).unwrap();
+        assert_eq!(lists.len(), 2);
+        assert_eq!(words(only(&lists[0])), ["a"]);
+        assert_eq!(lists[1].items.len(), 2);
+        // A line may also continue after `&&`
+        assert_eq!(parse("a &&\nb").items.len(), 2);
+    }
+
+    #[test]
+    fn aliases_expand_where_commands_start() {
+        let aliases = [("ll", "ls -l"), ("go", "echo x; ll")];
+        let lists = parse_with("ll a; echo ll; go", &aliases).unwrap();
+        let list = &lists[0];
+        assert_eq!(words(&list.items[0].1.commands[0]), ["ls", "-l", "a"]);
+        assert_eq!(words(&list.items[1].1.commands[0]), ["echo", "ll"]);
+        assert_eq!(words(&list.items[2].1.commands[0]), ["echo", "x"]);
+        assert_eq!(words(&list.items[3].1.commands[0]), ["ls", "-l"]);
+    }
+
+    #[test]
+    fn self_referential_aliases_stop() {
+        let list = &parse_with("ls", &[("ls", "ls -F")]).unwrap()[0];
+        assert_eq!(words(only(list)), ["ls", "-F"]);
+        // Each alias expands once, however the chain loops back
+        let aliases = [("a", "b 1"), ("b", "a 2")];
+        let list = &parse_with("a", &aliases).unwrap()[0];
+        assert_eq!(words(only(list)), ["a", "2", "1"]);
+        // Once its text is used up the alias may expand again
+        let list = &parse_with("ls; ls", &[("ls", "ls -F")]).unwrap()[0];
+        assert_eq!(words(&list.items[1].1.commands[0]), ["ls", "-F"]);
+    }
+
+    #[test]
+    fn nested_compound_commands() {
+        let list = parse(concat!(
+            "if a; then\n",
+            "  for x in 1 2; do\n",
+            "    while b; do c; done\n",
+            "  done\n",
+            "elif d\n",
+            "then e\n",
+            "else\n",
+            "  until f; do g; done\n",
+            "fi",
+        ));
+        let Command::If { branches, otherwise } = only(&list) else {
+            panic!("not an if");
+        };
+        assert_eq!(branches.len(), 2);
+        assert_eq!(words(only(&branches[0].0)), ["a"]);
+        let Command::For { var, words: items, body } = only(&branches[0].1) else {
+            panic!("not a for");
+        };
+        assert_eq!(var, "x");
+        let items: Vec<String> = items.as_ref().unwrap().iter().map(Word::as_source).collect();
+        assert_eq!(items, ["1", "2"]);
+        let Command::While { condition, body, until: false } = only(body) else {
+            panic!("not a while");
+        };
+        assert_eq!(words(only(condition)), ["b"]);
+        assert_eq!(words(only(body)), ["c"]);
+        assert_eq!(words(only(&branches[1].0)), ["d"]);
+        assert_eq!(words(only(&branches[1].1)), ["e"]);
+        let Some(otherwise) = otherwise else {
+            panic!("no else");
+        };
+        assert!(matches!(only(otherwise), Command::While { until: true, .. }));
+    }
+
+    #[test]
+    fn functions_and_groups() {
+        let lists = parse_with("f() {\n  echo a\n  echo b\n}\n{ f; f; } | wc", &[]).unwrap();
+        let Command::Function { name, body } = only(&lists[0]) else {
+            panic!("not a function");
+        };
+        assert_eq!(name, "f");
+        assert_eq!(body.items.len(), 2);
+        let pipeline = &lists[1].items[0].1;
+        assert!(matches!(&pipeline.commands[0], Command::Group(list) if list.items.len() == 2));
+        assert_eq!(words(&pipeline.commands[1]), ["wc"]);
+    }
+
+    #[test]
+    fn unfinished_compound_commands() {
+        let inputs = [
+            "if a; then b",
+            "if a; then fi",
+            "for x in 1; do",
+            "while a; do b",
+            "{ a",
+            "{ }",
+            "fi",
+            "done",
+        ];
+        for input in inputs {
+            assert!(parse_with(input, &[]).is_err(), "{:?} parsed", input);
+        }
+        assert_eq!(
+            parse_with("if a; then b; fi; fi", &[]).unwrap_err(),
+            "rsh: syntax error near unexpected token `fi'"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Prompt - expands the escapes in `$PS1`
+
+use ratatui::{style::Style, text::Span};
+
+use super::env::Environment;
+use super::filesystem::display_path;
+use crate::theme::Theme;
+
+/// The prompt as coloured spans. Supported escapes:
+///
+/// - `\u` user name, `\h` host name up to the first `.`, `\H` all of it
+/// - `\w` working directory with `~` for home, `\W` just its last part
+/// - `\$` a `$` (there is no root to get `#`), red when the last
+///   command failed
+/// - `\\` a backslash; `\[` and `\]` are dropped
+///
+/// Anything else is shown as typed, in a muted colour.
+pub fn spans(env: &Environment, cwd: &str) -> Vec<Span<'static>> {
+    let ps1 = env.get("PS1").unwrap_or_default();
+    let hostname = env.get("HOSTNAME").unwrap_or_default();
+    let mut spans = Vec::new();
+    let mut text = String::new();
+    let mut chars = ps1.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        let (value, color) = match chars.next() {
+            Some('u') => (env.get("USER").unwrap_or_default().to_string(), Theme::GUEST),
+            Some('h') => (hostname.split('.').next().unwrap_or_default().to_string(), Theme::SECONDARY),
+            Some('H') => (hostname.to_string(), Theme::SECONDARY),
+            Some('w') => (display_path(cwd), Theme::SECONDARY),
+            Some('W') => (basename(cwd), Theme::SECONDARY),
+            Some('$') if env.status() != 0 => ("$".to_string(), Theme::ERROR),
+            Some('$') => ("$".to_string(), Theme::SECONDARY),
+            Some('[' | ']') => continue,
+            Some('\\') => {
+                text.push('\\');
+                continue;
+            }
+            Some(other) => {
+                text.push('\\');
+                text.push(other);
+                continue;
+            }
+            None => {
+                text.push('\\');
+                break;
+            }
+        };
+        if !text.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut text), Style::default().fg(Theme::MUTED)));
+        }
+        spans.push(Span::styled(value, Style::default().fg(color)));
+    }
+    if !text.is_empty() {
+        spans.push(Span::styled(text, Style::default().fg(Theme::MUTED)));
+    }
+    spans
+}
+
+/// The prompt as plain text
+pub fn text(env: &Environment, cwd: &str) -> String {
+    spans(env, cwd).iter().map(|span| span.content.as_ref()).collect()
+}
+
+/// Last part of the working directory, `~` for home and `/` for the root
+fn basename(cwd: &str) -> String {
+    let path = display_path(cwd);
+    match path.rsplit_once('/') {
+        Some((_, "")) | None => path,
+        Some((_, name)) => name.to_string(),
+    }
+}
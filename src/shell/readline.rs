@@ -0,0 +1,533 @@
+//! Line editor for the prompt - emacs-style bindings by default, vi
+//! bindings after `set -o vi`. Positions are byte offsets that always sit
+//! on grapheme boundaries, so emoji and combining characters move and
+//! delete as one unit.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// What the shell should do after a key press
+#[derive(Clone, Copy, PartialEq)]
+pub enum Action {
+    /// Nothing beyond what the editor already did
+    None,
+    /// The line was changed
+    Edited,
+    Submit,
+    Complete,
+    HistoryPrev,
+    HistoryNext,
+    /// Start a Ctrl-R search
+    SearchHistory,
+    ClearScreen,
+    /// Ctrl-D on an empty line
+    EndOfFile,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Emacs,
+    ViInsert,
+    ViNormal,
+}
+
+/// Rough word classes, for word motions
+#[derive(PartialEq)]
+enum Class {
+    Space,
+    Word,
+    Punct,
+}
+
+fn class(grapheme: &str) -> Class {
+    match grapheme.chars().next() {
+        Some(c) if c.is_whitespace() => Class::Space,
+        Some(c) if c.is_alphanumeric() || c == '_' => Class::Word,
+        _ => Class::Punct,
+    }
+}
+
+/// The line being edited at the prompt
+pub struct LineEditor {
+    buffer: String,
+    /// Byte offset of the cursor
+    cursor: usize,
+    /// Text removed by the last kill, for Ctrl-Y and `p`
+    kill_buffer: String,
+    mode: Mode,
+    /// vi operator or `r` waiting for its motion or character
+    pending: Option<char>,
+}
+
+impl LineEditor {
+    pub fn new() -> Self {
+        Self {
+            buffer: String::new(),
+            cursor: 0,
+            kill_buffer: String::new(),
+            mode: Mode::Emacs,
+            pending: None,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.buffer
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Replace the whole line, with the cursor at the end
+    pub fn set_text(&mut self, text: &str) {
+        self.buffer = text.to_string();
+        self.cursor = self.buffer.len();
+        if self.mode == Mode::ViNormal {
+            self.clamp_normal();
+        }
+    }
+
+    /// Take the line for execution, leaving the editor empty. vi mode
+    /// starts every line in insert mode.
+    pub fn take(&mut self) -> String {
+        self.cursor = 0;
+        self.pending = None;
+        if self.mode == Mode::ViNormal {
+            self.mode = Mode::ViInsert;
+        }
+        std::mem::take(&mut self.buffer)
+    }
+
+    /// Replace the text from byte `start` up to the cursor
+    pub fn replace_before_cursor(&mut self, start: usize, text: &str) {
+        self.buffer.replace_range(start..self.cursor, text);
+        self.cursor = start + text.len();
+    }
+
+    /// Switch between emacs and vi bindings
+    pub fn set_vi(&mut self, vi: bool) {
+        self.pending = None;
+        self.mode = match (vi, self.mode) {
+            (false, _) => Mode::Emacs,
+            (true, Mode::Emacs) => Mode::ViInsert,
+            (true, mode) => mode,
+        };
+    }
+
+    /// Whether the cursor is a vi command-mode block
+    pub fn in_normal_mode(&self) -> bool {
+        self.mode == Mode::ViNormal
+    }
+
+    /// The line split around the grapheme under the cursor (empty at the end)
+    pub fn split_at_cursor(&self) -> (&str, &str, &str) {
+        let end = self.next_boundary(self.cursor);
+        (
+            &self.buffer[..self.cursor],
+            &self.buffer[self.cursor..end],
+            &self.buffer[end..],
+        )
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        match self.mode {
+            Mode::Emacs => self.emacs_key(key),
+            Mode::ViInsert => self.vi_insert_key(key),
+            Mode::ViNormal => self.vi_normal_key(key),
+        }
+    }
+
+    fn emacs_key(&mut self, key: KeyEvent) -> Action {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+        match key.code {
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.buffer.len(),
+            KeyCode::Char('b') if ctrl => self.cursor = self.prev_boundary(self.cursor),
+            KeyCode::Char('f') if ctrl => self.cursor = self.next_boundary(self.cursor),
+            KeyCode::Char('b') if alt => self.cursor = self.word_start_before(self.cursor),
+            KeyCode::Char('f') if alt => self.cursor = self.word_end_after(self.cursor),
+            KeyCode::Left if ctrl || alt => self.cursor = self.word_start_before(self.cursor),
+            KeyCode::Right if ctrl || alt => self.cursor = self.word_end_after(self.cursor),
+            KeyCode::Char('d') if ctrl => {
+                if self.buffer.is_empty() {
+                    return Action::EndOfFile;
+                }
+                return self.delete_forward();
+            }
+            KeyCode::Char('h') if ctrl => return self.delete_back(),
+            KeyCode::Char('w') if ctrl => {
+                return self.kill(self.blank_word_start_before(self.cursor), self.cursor);
+            }
+            KeyCode::Char('u') if ctrl => return self.kill(0, self.cursor),
+            KeyCode::Char('k') if ctrl => return self.kill(self.cursor, self.buffer.len()),
+            KeyCode::Char('d') if alt => return self.kill(self.cursor, self.word_end_after(self.cursor)),
+            KeyCode::Backspace if alt => {
+                return self.kill(self.word_start_before(self.cursor), self.cursor);
+            }
+            KeyCode::Char('y') if ctrl => return self.yank(),
+            KeyCode::Char('l') if ctrl => return Action::ClearScreen,
+            KeyCode::Char('p') if ctrl => return Action::HistoryPrev,
+            KeyCode::Char('n') if ctrl => return Action::HistoryNext,
+            KeyCode::Char('r') if ctrl => return Action::SearchHistory,
+            KeyCode::Char('j') | KeyCode::Char('m') if ctrl => return Action::Submit,
+            KeyCode::Char(_) if ctrl || alt => {}
+            _ => return self.common_key(key),
+        }
+        Action::None
+    }
+
+    /// Keys that behave the same in emacs mode and vi insert mode
+    fn common_key(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Char(c) => {
+                self.insert(c.encode_utf8(&mut [0; 4]));
+                return Action::Edited;
+            }
+            KeyCode::Backspace => return self.delete_back(),
+            KeyCode::Delete => return self.delete_forward(),
+            KeyCode::Left => self.cursor = self.prev_boundary(self.cursor),
+            KeyCode::Right => self.cursor = self.next_boundary(self.cursor),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.buffer.len(),
+            KeyCode::Up => return Action::HistoryPrev,
+            KeyCode::Down => return Action::HistoryNext,
+            KeyCode::Tab => return Action::Complete,
+            KeyCode::Enter => return Action::Submit,
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn vi_insert_key(&mut self, key: KeyEvent) -> Action {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = Mode::ViNormal;
+                self.cursor = self.prev_boundary(self.cursor);
+                Action::None
+            }
+            KeyCode::Char('w') if ctrl => {
+                self.kill(self.blank_word_start_before(self.cursor), self.cursor)
+            }
+            KeyCode::Char('u') if ctrl => self.kill(0, self.cursor),
+            KeyCode::Char('h') if ctrl => self.delete_back(),
+            KeyCode::Char('l') if ctrl => Action::ClearScreen,
+            KeyCode::Char('r') if ctrl => Action::SearchHistory,
+            KeyCode::Char('d') if ctrl && self.buffer.is_empty() => Action::EndOfFile,
+            KeyCode::Char(_) if ctrl => Action::None,
+            _ => self.common_key(key),
+        }
+    }
+
+    fn vi_normal_key(&mut self, key: KeyEvent) -> Action {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let c = match key.code {
+            KeyCode::Char('l') if ctrl => return Action::ClearScreen,
+            KeyCode::Char('r') if ctrl => return Action::SearchHistory,
+            KeyCode::Char('d') if ctrl && self.buffer.is_empty() => return Action::EndOfFile,
+            KeyCode::Char(_) if ctrl => return Action::None,
+            KeyCode::Char(c) => c,
+            KeyCode::Left => 'h',
+            KeyCode::Right => 'l',
+            KeyCode::Home => '0',
+            KeyCode::End => '$',
+            KeyCode::Up => 'k',
+            KeyCode::Down => 'j',
+            KeyCode::Backspace => 'h',
+            KeyCode::Delete => 'x',
+            KeyCode::Enter => return Action::Submit,
+            KeyCode::Esc => {
+                self.pending = None;
+                return Action::None;
+            }
+            _ => return Action::None,
+        };
+
+        match self.pending.take() {
+            Some('r') => self.replace_char(c),
+            Some(op) => self.operator(op, c),
+            None => self.vi_command(c),
+        }
+    }
+
+    fn vi_command(&mut self, c: char) -> Action {
+        let action = match c {
+            'i' => return self.enter_insert(self.cursor),
+            'a' => return self.enter_insert(self.next_boundary(self.cursor)),
+            'I' => return self.enter_insert(0),
+            'A' => return self.enter_insert(self.buffer.len()),
+            'x' => self.kill(self.cursor, self.next_boundary(self.cursor)),
+            'X' => self.kill(self.prev_boundary(self.cursor), self.cursor),
+            'D' => self.kill(self.cursor, self.buffer.len()),
+            'C' => {
+                let action = self.kill(self.cursor, self.buffer.len());
+                self.mode = Mode::ViInsert;
+                return action;
+            }
+            'S' => {
+                let action = self.kill(0, self.buffer.len());
+                self.mode = Mode::ViInsert;
+                return action;
+            }
+            'p' => {
+                self.cursor = self.next_boundary(self.cursor);
+                self.yank()
+            }
+            'P' => self.yank(),
+            'd' | 'c' | 'y' | 'r' => {
+                self.pending = Some(c);
+                return Action::None;
+            }
+            'k' => return Action::HistoryPrev,
+            'j' => return Action::HistoryNext,
+            _ => {
+                if let Some(target) = self.motion(c) {
+                    self.cursor = target;
+                }
+                Action::None
+            }
+        };
+        self.clamp_normal();
+        action
+    }
+
+    /// `d`, `c` or `y` applied to a motion; doubling the operator means the
+    /// whole line
+    fn operator(&mut self, op: char, motion: char) -> Action {
+        let (start, end) = if motion == op {
+            (0, self.buffer.len())
+        } else {
+            // `cw` changes to the end of the word, like vi
+            let motion = if op == 'c' && motion == 'w' { 'e' } else { motion };
+            let Some(target) = self.motion(motion) else {
+                return Action::None;
+            };
+            let target = if motion == 'e' { self.next_boundary(target) } else { target };
+            (self.cursor.min(target), self.cursor.max(target))
+        };
+
+        if op == 'y' {
+            self.kill_buffer = self.buffer[start..end].to_string();
+            return Action::None;
+        }
+        let action = self.kill(start, end);
+        if op == 'c' {
+            self.mode = Mode::ViInsert;
+        } else {
+            self.clamp_normal();
+        }
+        action
+    }
+
+    /// Where a vi motion key moves the cursor to
+    fn motion(&self, c: char) -> Option<usize> {
+        Some(match c {
+            'h' => self.prev_boundary(self.cursor),
+            'l' => self.next_boundary(self.cursor),
+            '0' => 0,
+            '^' => self.buffer.len() - self.buffer.trim_start().len(),
+            '$' => self.buffer.len(),
+            'w' => self.vi_word_forward(self.cursor),
+            'b' => self.vi_word_back(self.cursor),
+            'e' => self.vi_word_end(self.cursor),
+            _ => return None,
+        })
+    }
+
+    fn replace_char(&mut self, c: char) -> Action {
+        let end = self.next_boundary(self.cursor);
+        if end == self.cursor {
+            return Action::None;
+        }
+        self.buffer.replace_range(self.cursor..end, c.encode_utf8(&mut [0; 4]));
+        Action::Edited
+    }
+
+    fn enter_insert(&mut self, at: usize) -> Action {
+        self.cursor = at;
+        self.mode = Mode::ViInsert;
+        Action::None
+    }
+
+    /// In vi command mode the cursor sits on a character, never past the end
+    fn clamp_normal(&mut self) {
+        if self.cursor >= self.buffer.len() {
+            self.cursor = self.prev_boundary(self.buffer.len());
+        }
+    }
+
+    fn insert(&mut self, text: &str) {
+        self.buffer.insert_str(self.cursor, text);
+        self.cursor += text.len();
+    }
+
+    fn delete_back(&mut self) -> Action {
+        let start = self.prev_boundary(self.cursor);
+        if start == self.cursor {
+            return Action::None;
+        }
+        self.buffer.replace_range(start..self.cursor, "");
+        self.cursor = start;
+        Action::Edited
+    }
+
+    fn delete_forward(&mut self) -> Action {
+        let end = self.next_boundary(self.cursor);
+        if end == self.cursor {
+            return Action::None;
+        }
+        self.buffer.replace_range(self.cursor..end, "");
+        Action::Edited
+    }
+
+    /// Remove `start..end` into the kill buffer
+    fn kill(&mut self, start: usize, end: usize) -> Action {
+        if start == end {
+            return Action::None;
+        }
+        self.kill_buffer = self.buffer[start..end].to_string();
+        self.buffer.replace_range(start..end, "");
+        self.cursor = start;
+        Action::Edited
+    }
+
+    fn yank(&mut self) -> Action {
+        if self.kill_buffer.is_empty() {
+            return Action::None;
+        }
+        let text = self.kill_buffer.clone();
+        self.insert(&text);
+        if self.mode == Mode::ViNormal {
+            self.cursor = self.prev_boundary(self.cursor);
+        }
+        Action::Edited
+    }
+
+    fn prev_boundary(&self, pos: usize) -> usize {
+        self.buffer[..pos]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    fn next_boundary(&self, pos: usize) -> usize {
+        self.buffer[pos..]
+            .graphemes(true)
+            .next()
+            .map_or(pos, |g| pos + g.len())
+    }
+
+    /// Graphemes before `pos`, nearest first
+    fn before(&self, pos: usize) -> impl Iterator<Item = (usize, &str)> {
+        self.buffer[..pos].grapheme_indices(true).rev()
+    }
+
+    /// Graphemes from `pos` on
+    fn after(&self, pos: usize) -> impl Iterator<Item = (usize, &str)> {
+        self.buffer[pos..].grapheme_indices(true).map(move |(i, g)| (pos + i, g))
+    }
+
+    /// Start of the word before `pos` (Alt-B, vi `b`)
+    fn word_start_before(&self, pos: usize) -> usize {
+        let mut start = pos;
+        let mut in_word = false;
+        for (i, g) in self.before(pos) {
+            let is_word = class(g) == Class::Word;
+            if in_word && !is_word {
+                break;
+            }
+            in_word |= is_word;
+            start = i;
+        }
+        start
+    }
+
+    /// End of the word after `pos` (Alt-F)
+    fn word_end_after(&self, pos: usize) -> usize {
+        let mut end = pos;
+        let mut in_word = false;
+        for (i, g) in self.after(pos) {
+            let is_word = class(g) == Class::Word;
+            if in_word && !is_word {
+                break;
+            }
+            in_word |= is_word;
+            end = i + g.len();
+        }
+        end
+    }
+
+    /// Start of the whitespace-delimited word before `pos` (Ctrl-W)
+    fn blank_word_start_before(&self, pos: usize) -> usize {
+        let mut start = pos;
+        let mut in_word = false;
+        for (i, g) in self.before(pos) {
+            let is_space = class(g) == Class::Space;
+            if in_word && is_space {
+                break;
+            }
+            in_word |= !is_space;
+            start = i;
+        }
+        start
+    }
+
+    /// vi `w`: start of the next word, where a run of punctuation also
+    /// counts as a word
+    fn vi_word_forward(&self, pos: usize) -> usize {
+        let mut graphemes = self.after(pos);
+        let Some((_, first)) = graphemes.next() else {
+            return pos;
+        };
+        let first = class(first);
+        for (i, g) in graphemes {
+            let current = class(g);
+            if current != Class::Space && current != first {
+                return i;
+            }
+            if current == Class::Space {
+                // Past the first run, any non-space starts the next word
+                return self
+                    .after(i)
+                    .find(|(_, g)| class(g) != Class::Space)
+                    .map_or(self.buffer.len(), |(j, _)| j);
+            }
+        }
+        self.buffer.len()
+    }
+
+    /// vi `b`: start of the current or previous word
+    fn vi_word_back(&self, pos: usize) -> usize {
+        let mut graphemes = self.before(pos).skip_while(|(_, g)| class(g) == Class::Space);
+        let Some((mut start, first)) = graphemes.next() else {
+            return 0;
+        };
+        let first = class(first);
+        for (i, g) in graphemes {
+            if class(g) != first {
+                break;
+            }
+            start = i;
+        }
+        start
+    }
+
+    /// vi `e`: last grapheme of the current or next word
+    fn vi_word_end(&self, pos: usize) -> usize {
+        let start = self.next_boundary(pos);
+        let mut graphemes = self.after(start).skip_while(|(_, g)| class(g) == Class::Space);
+        let Some((mut end, first)) = graphemes.next() else {
+            return pos;
+        };
+        let first = class(first);
+        for (i, g) in graphemes {
+            if class(g) != first {
+                break;
+            }
+            end = i;
+        }
+        end
+    }
+}
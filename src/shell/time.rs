@@ -0,0 +1,69 @@
+//! Clock and calendar helpers. All times are UTC seconds since the Unix epoch.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Roughly six months, the age at which `ls` shows a year instead of a time
+const RECENT_SECS: u64 = 60 * 60 * 24 * 365 / 2;
+
+/// Broken-down UTC time
+pub struct DateTime {
+    pub year: i64,
+    /// 1-12
+    pub month: u32,
+    /// 1-31
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+}
+
+/// Current time
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Convert seconds since the epoch to a calendar date and time
+pub fn civil(secs: u64) -> DateTime {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // Howard Hinnant's days-to-civil algorithm
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    DateTime {
+        year,
+        month,
+        day,
+        hour: (rem / 3600) as u32,
+        minute: (rem % 3600 / 60) as u32,
+    }
+}
+
+/// Short month name, e.g. `Jan`
+fn month_name(month: u32) -> &'static str {
+    MONTHS[(month as usize).saturating_sub(1) % 12]
+}
+
+/// `ls -l` timestamp: `Jan 29 15:30` for recent files, `Jan 29  2025` otherwise
+pub fn format_ls_time(mtime: u64, now: u64) -> String {
+    let t = civil(mtime);
+    let recent = mtime <= now && now - mtime < RECENT_SECS;
+    if recent {
+        format!("{} {:02} {:02}:{:02}", month_name(t.month), t.day, t.hour, t.minute)
+    } else {
+        format!("{} {:02} {:>5}", month_name(t.month), t.day, t.year)
+    }
+}
@@ -1,4 +1,5 @@
 //! Custom color theme for the portfolio
+
 use ratatui::style::Color;
 
 /// Custom color palette from portfolio brand
@@ -17,9 +18,6 @@ impl Theme {
     /// Soft purple - text accent
     pub const ACCENT: Color = Color::Rgb(180, 150, 220); // #B496DC
     
-    /// Deep teal variant
-    pub const PRIMARY_DARK: Color = Color::Rgb(60, 160, 140); // #3CA08C
-    
     /// Background (deep charcoal - easier on eyes)
     pub const BACKGROUND: Color = Color::Rgb(18, 18, 18); // #121212
     
@@ -41,12 +39,6 @@ impl Theme {
     /// Border color (teal)
     pub const BORDER: Color = Color::Rgb(80, 200, 180); // #50C8B4
     
-    /// Selected/active item background
-    pub const SELECTED_BG: Color = Color::Rgb(80, 200, 180); // #50C8B4
-    
-    /// Selected/active item foreground
-    pub const SELECTED_FG: Color = Color::Rgb(18, 18, 18); // #121212
-
-    /// 
+    /// Muted indigo - prompt user name
     pub const GUEST: Color = Color::Rgb(62, 63, 91); // #3E3F5B
 }
\ No newline at end of file
@@ -1,11 +1,14 @@
 //! Reusable UI components with themed styling
+
 use ratatui::{
-    layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders},
 };
 
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
 use crate::theme::Theme;
 
 /// Creates a themed block with title
@@ -17,13 +20,6 @@ pub fn themed_block<'a>(title: &'a str) -> Block<'a> {
         .title_style(Style::default().fg(Theme::SECONDARY).add_modifier(Modifier::BOLD))
 }
 
-/// Creates a themed block without title
-pub fn themed_block_plain<'a>() -> Block<'a> {
-    Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::BORDER))
-}
-
 /// Creates a footer line with key bindings
 pub fn footer_keybinds<'a>(bindings: &[(&'a str, &'a str)]) -> Line<'a> {
     let mut spans = vec![Span::raw(" ")];
@@ -78,14 +74,6 @@ pub fn error_text(text: &str) -> Line<'static> {
     ))
 }
 
-/// Creates a success message line
-pub fn success_text(text: &str) -> Line<'static> {
-    Line::from(Span::styled(
-        text.to_string(),
-        Style::default().fg(Theme::SUCCESS),
-    ))
-}
-
 /// Creates a warning message line
 pub fn warning_text(text: &str) -> Line<'static> {
     Line::from(Span::styled(
@@ -94,75 +82,48 @@ pub fn warning_text(text: &str) -> Line<'static> {
     ))
 }
 
-/// Creates a tag/badge span
-pub fn tag(text: &str) -> Span<'static> {
-    Span::styled(
-        format!(" {} ", text),
-        Style::default()
-            .fg(Theme::BACKGROUND)
-            .bg(Theme::ACCENT),
-    )
-}
-
-/// Creates a themed list item
-pub fn list_item(text: &str, selected: bool) -> ListItem<'static> {
-    let style = if selected {
-        Style::default()
-            .fg(Theme::SELECTED_FG)
-            .bg(Theme::SELECTED_BG)
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Theme::FOREGROUND)
-    };
-    
-    ListItem::new(Line::from(Span::styled(text.to_string(), style)))
-}
-
-/// Creates a themed list item with multiple lines
-pub fn list_item_multi(lines: Vec<Line<'static>>, selected: bool) -> ListItem<'static> {
-    if selected {
-        let styled_lines: Vec<Line> = lines
-            .into_iter()
-            .map(|mut line| {
-                for span in &mut line.spans {
-                    span.style = Style::default()
-                        .fg(Theme::SELECTED_FG)
-                        .bg(Theme::SELECTED_BG);
-                }
-                line
-            })
-            .collect();
-        ListItem::new(styled_lines)
-    } else {
-        ListItem::new(lines)
-    }
-}
-
-/// Style for selected items
-pub fn selected_style() -> Style {
-    Style::default()
-        .fg(Theme::SELECTED_FG)
-        .bg(Theme::SELECTED_BG)
-        .add_modifier(Modifier::BOLD)
-}
-
-/// Style for normal text
-pub fn normal_style() -> Style {
-    Style::default().fg(Theme::FOREGROUND)
+/// Create an OSC 8 hyperlink (clickable in modern terminals)
+pub fn osc8_link(url: &str, text: &str) -> String {
+    format!("\x1b]8;;{}\x07{}\x1b]8;;\x07", url, text)
 }
 
 // =============================================================================
 // UI UTILITIES
 // =============================================================================
 
-/// Center text within a given width
-pub fn center_text(text: &str, width: usize) -> String {
-    let text_len = text.chars().count();
-    if text_len >= width {
-        text.to_string()
-    } else {
-        let padding = (width - text_len) / 2;
-        format!("{}{}", " ".repeat(padding), text)
-    }
+/// Flatten a styled line back into plain text
+pub fn line_to_string(line: &Line) -> String {
+    line.spans.iter().map(|span| span.content.as_ref()).collect()
 }
 
+/// Split a line into rows of at most `width` columns, breaking between
+/// graphemes like a terminal does. Styles carry over to every row.
+pub fn wrap_line(line: &Line<'static>, width: usize) -> Vec<Line<'static>> {
+    if width == 0 {
+        return vec![line.clone()];
+    }
+
+    let mut rows = Vec::new();
+    let mut row: Vec<Span<'static>> = Vec::new();
+    let mut col = 0;
+    for span in &line.spans {
+        let mut text = String::new();
+        for grapheme in span.content.graphemes(true) {
+            let w = grapheme.width();
+            if col + w > width && col > 0 {
+                if !text.is_empty() {
+                    row.push(Span::styled(std::mem::take(&mut text), span.style));
+                }
+                rows.push(Line::from(std::mem::take(&mut row)).style(line.style));
+                col = 0;
+            }
+            text.push_str(grapheme);
+            col += w;
+        }
+        if !text.is_empty() {
+            row.push(Span::styled(text, span.style));
+        }
+    }
+    rows.push(Line::from(row).style(line.style));
+    rows
+}